@@ -1705,7 +1705,7 @@ impl<'a, 'hir> LoweringContext<'a, 'hir> {
                 },
             )
         });
-        let init = l.init.as_ref().map(|e| self.lower_expr(e));
+        let init = l.kind.init().map(|e| self.lower_expr(e));
         (
             hir::Local {
                 hir_id: self.lower_node_id(l.id),
@@ -2206,13 +2206,14 @@ impl<'a, 'hir> LoweringContext<'a, 'hir> {
 
                 (hir::ParamName::Plain(param.ident), kind)
             }
-            GenericParamKind::Const { ref ty, kw_span: _ } => {
+            GenericParamKind::Const { ref ty, kw_span: _, ref default } => {
                 let ty = self
                     .with_anonymous_lifetime_mode(AnonymousLifetimeMode::ReportError, |this| {
                         this.lower_ty(&ty, ImplTraitContext::disallowed())
                     });
+                let default = default.as_ref().map(|def| self.lower_anon_const(def));
 
-                (hir::ParamName::Plain(param.ident), hir::GenericParamKind::Const { ty })
+                (hir::ParamName::Plain(param.ident), hir::GenericParamKind::Const { ty, default })
             }
         };
 
@@ -2301,29 +2302,100 @@ impl<'a, 'hir> LoweringContext<'a, 'hir> {
     }
 
     fn lower_block_noalloc(&mut self, b: &Block, targeted_by_break: bool) -> hir::Block<'hir> {
+        let (stmts, expr) = self.lower_stmts(&b.stmts);
+
+        hir::Block {
+            hir_id: self.lower_node_id(b.id),
+            stmts,
+            expr,
+            rules: self.lower_block_check_mode(&b.rules),
+            span: b.span,
+            targeted_by_break,
+        }
+    }
+
+    fn lower_stmts(
+        &mut self,
+        mut ast_stmts: &[Stmt],
+    ) -> (&'hir [hir::Stmt<'hir>], Option<&'hir hir::Expr<'hir>>) {
         let mut stmts = vec![];
         let mut expr: Option<&'hir _> = None;
 
-        for (index, stmt) in b.stmts.iter().enumerate() {
-            if index == b.stmts.len() - 1 {
-                if let StmtKind::Expr(ref e) = stmt.kind {
-                    expr = Some(self.lower_expr(e));
-                } else {
+        while let [stmt, tail @ ..] = ast_stmts {
+            match stmt.kind {
+                StmtKind::Local(ref local) => {
+                    if let LocalKind::InitElse(ref init, ref els) = local.kind {
+                        // The remaining statements end up inside of the `let...else`.
+                        expr = Some(self.lower_let_else(stmt, local, init, els, tail));
+                        break;
+                    }
                     stmts.extend(self.lower_stmt(stmt));
                 }
-            } else {
-                stmts.extend(self.lower_stmt(stmt));
+                StmtKind::Expr(ref e) if tail.is_empty() => expr = Some(self.lower_expr(e)),
+                _ => stmts.extend(self.lower_stmt(stmt)),
             }
+            ast_stmts = tail;
         }
 
-        hir::Block {
-            hir_id: self.lower_node_id(b.id),
-            stmts: self.arena.alloc_from_iter(stmts),
-            expr,
-            rules: self.lower_block_check_mode(&b.rules),
-            span: b.span,
-            targeted_by_break,
+        (self.arena.alloc_from_iter(stmts), expr)
+    }
+
+    /// Lowers `let PAT: TY = INIT else { ELSE }; TAIL` to
+    ///
+    /// ```ignore (pseudo-rust)
+    /// match INIT: TY {
+    ///     PAT => { TAIL }
+    ///     _ => { ELSE }
+    /// }
+    /// ```
+    ///
+    /// so that the bindings of `PAT` are in scope in the statements following the `let...else`.
+    fn lower_let_else(
+        &mut self,
+        stmt: &Stmt,
+        local: &Local,
+        init: &Expr,
+        els: &Block,
+        tail: &[Stmt],
+    ) -> &'hir hir::Expr<'hir> {
+        let mut scrutinee = self.lower_expr(init);
+        if let Some(ref ty) = local.ty {
+            let ty = self.lower_ty(ty, ImplTraitContext::Disallowed(ImplTraitPosition::Binding));
+            let kind = hir::ExprKind::Type(scrutinee, ty);
+            scrutinee = self.arena.alloc(self.expr(init.span, kind, AttrVec::new()));
         }
+
+        // `PAT => { TAIL }`:
+        let then_arm = {
+            let pat = self.lower_pat(&local.pat);
+            let (stmts, expr) = self.lower_stmts(tail);
+            let span = match tail {
+                [first, .., last] => first.span.to(last.span),
+                [only] => only.span,
+                [] => stmt.span.shrink_to_hi(),
+            };
+            let block = self.block_all(span, stmts, expr);
+            let expr = self.expr_block(block, AttrVec::new());
+            self.arm(pat, self.arena.alloc(expr))
+        };
+
+        // `_ => { ELSE }`:
+        let else_arm = {
+            let pat = self.pat_wild(local.pat.span);
+            let expr = self.lower_block_expr(els);
+            self.arm(pat, self.arena.alloc(expr))
+        };
+
+        self.arena.alloc(hir::Expr {
+            hir_id: self.lower_node_id(stmt.id),
+            kind: hir::ExprKind::Match(
+                scrutinee,
+                arena_vec![self; then_arm, else_arm],
+                hir::MatchSource::LetElseDesugar,
+            ),
+            span: stmt.span,
+            attrs: local.attrs.clone(),
+        })
     }
 
     /// Lowers a block directly to an expression, presuming that it
@@ -258,6 +258,8 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
                 gate_doc!(
                     include => external_doc
                     cfg => doc_cfg
+                    cfg_hide => doc_cfg
+                    auto_cfg => doc_cfg
                     masked => doc_masked
                     spotlight => doc_spotlight
                     keyword => doc_keyword
@@ -622,6 +624,9 @@ pub fn check_crate(krate: &ast::Crate, sess: &Session) {
     }
     gate_all!(if_let_guard, "`if let` guard is not implemented");
     gate_all!(let_chains, "`let` expressions in this position are experimental");
+    gate_all!(let_else, "`let...else` statements are unstable");
+    gate_all!(destructuring_assignment, "destructuring assignments are unstable");
+    gate_all!(const_generics_defaults, "default values for const generic parameters are unstable");
     gate_all!(async_closure, "async closures are unstable");
     gate_all!(generators, "yield syntax is experimental");
     gate_all!(or_patterns, "or-patterns syntax is experimental");
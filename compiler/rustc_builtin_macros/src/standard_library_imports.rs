@@ -32,7 +32,7 @@ pub fn inject(
     let expn_id = resolver.expansion_for_ast_pass(
         DUMMY_SP,
         AstPass::StdImports,
-        &[sym::prelude_import],
+        &[sym::prelude_import, sym::prelude_2021],
         None,
     );
     let span = DUMMY_SP.with_def_site_ctxt(expn_id);
@@ -59,10 +59,14 @@ pub fn inject(
     // the one with the prelude.
     let name = names[0];
 
+    // The 2021 edition has its own prelude; earlier editions share `v1`.
+    let prelude =
+        if sess.parse_sess.edition >= Edition::Edition2021 { sym::rust_2021 } else { sym::v1 };
+
     let import_path = if rust_2018 {
-        [name, sym::prelude, sym::v1].iter().map(|symbol| Ident::new(*symbol, span)).collect()
+        [name, sym::prelude, prelude].iter().map(|symbol| Ident::new(*symbol, span)).collect()
     } else {
-        [kw::PathRoot, name, sym::prelude, sym::v1]
+        [kw::PathRoot, name, sym::prelude, prelude]
             .iter()
             .map(|symbol| Ident::new(*symbol, span))
             .collect()
@@ -5,11 +5,13 @@ use crate::util::check_builtin_macro_attribute;
 use rustc_ast as ast;
 use rustc_ast::attr;
 use rustc_ast_pretty::pprust;
+use rustc_errors::Applicability;
 use rustc_expand::base::*;
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::Span;
 
+use std::convert::TryFrom;
 use std::iter;
 
 // #[test_case] is used by custom test authors to mark tests
@@ -245,6 +247,23 @@ pub fn expand_test_or_bench(
                                         "allow_fail",
                                         cx.expr_bool(sp, should_fail(&cx.sess, &item)),
                                     ),
+                                    // timeout: None | Some(secs)
+                                    field(
+                                        "timeout",
+                                        match test_timeout(cx, &item) {
+                                            Some(secs) => cx.expr_some(
+                                                sp,
+                                                cx.expr_lit(
+                                                    sp,
+                                                    ast::LitKind::Int(
+                                                        secs.into(),
+                                                        ast::LitIntType::Unsigned(ast::UintTy::U64),
+                                                    ),
+                                                ),
+                                            ),
+                                            None => cx.expr_none(sp),
+                                        },
+                                    ),
                                     // should_panic: ...
                                     field(
                                         "should_panic",
@@ -323,6 +342,32 @@ fn item_path(mod_path: &[Ident], item_ident: &Ident) -> String {
         .join("::")
 }
 
+/// Reads the hard time limit, in seconds, set with `#[test_timeout(secs)]`.
+fn test_timeout(cx: &ExtCtxt<'_>, i: &ast::Item) -> Option<u64> {
+    let attr = cx.sess.find_by_name(&i.attrs, sym::test_timeout)?;
+    let secs = match attr.meta_item_list().as_deref() {
+        Some([ast::NestedMetaItem::Literal(lit)]) => match lit.kind {
+            ast::LitKind::Int(secs, _) => u64::try_from(secs).ok().filter(|&secs| secs != 0),
+            _ => None,
+        },
+        _ => None,
+    };
+    if secs.is_none() {
+        cx.sess
+            .parse_sess
+            .span_diagnostic
+            .struct_span_err(attr.span, "malformed `test_timeout` attribute input")
+            .span_suggestion(
+                attr.span,
+                "the argument must be a number of seconds",
+                "#[test_timeout(60)]".to_string(),
+                Applicability::HasPlaceholders,
+            )
+            .emit();
+    }
+    secs
+}
+
 enum ShouldPanic {
     No,
     Yes(Option<Symbol>),
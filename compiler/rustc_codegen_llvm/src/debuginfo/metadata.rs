@@ -999,7 +999,14 @@ pub fn compile_unit_metadata(
     let name_in_debuginfo = name_in_debuginfo.to_string_lossy();
     let work_dir = tcx.sess.working_dir.0.to_string_lossy();
     let flags = "\0";
-    let split_name = "";
+    let split_name = if tcx.sess.split_dwarf() {
+        tcx.output_filenames(LOCAL_CRATE)
+            .temp_path_dwo(Some(codegen_unit_name))
+            .to_string_lossy()
+            .into_owned()
+    } else {
+        String::new()
+    };
 
     // FIXME(#60020):
     //
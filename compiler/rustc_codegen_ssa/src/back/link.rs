@@ -15,7 +15,7 @@ use rustc_session::{filesearch, Session};
 use rustc_span::symbol::Symbol;
 use rustc_target::spec::crt_objects::{CrtObjects, CrtObjectsFallback};
 use rustc_target::spec::{LinkOutputKind, LinkerFlavor, LldFlavor};
-use rustc_target::spec::{PanicStrategy, RelocModel, RelroLevel};
+use rustc_target::spec::{PanicStrategy, RelocModel, RelroLevel, SplitDebuginfo};
 
 use super::archive::ArchiveBuilder;
 use super::command::Command;
@@ -114,6 +114,11 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
                     remove(sess, obj);
                 }
             }
+            if sess.opts.output_types.should_codegen() && !preserve_dwarf_objects(sess) {
+                for obj in codegen_results.modules.iter().filter_map(|m| m.dwarf_object.as_ref()) {
+                    remove(sess, obj);
+                }
+            }
             if let Some(ref metadata_module) = codegen_results.metadata_module {
                 if let Some(ref obj) = metadata_module.object {
                     remove(sess, obj);
@@ -647,13 +652,6 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
 
     match prog {
         Ok(prog) => {
-            fn escape_string(s: &[u8]) -> String {
-                str::from_utf8(s).map(|s| s.to_owned()).unwrap_or_else(|_| {
-                    let mut x = "Non-UTF-8 output: ".to_string();
-                    x.extend(s.iter().flat_map(|&b| ascii::escape_default(b)).map(char::from));
-                    x
-                })
-            }
             if !prog.status.success() {
                 let mut output = prog.stderr.clone();
                 output.extend_from_slice(&prog.stdout);
@@ -756,16 +754,93 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
     }
 
     // On macOS, debuggers need this utility to get run to do some munging of
-    // the symbols. Note, though, that if the object files are being preserved
-    // for their debug information there's no need for us to run dsymutil.
+    // the symbols. Note, though, that with unpacked split debuginfo the object
+    // files are preserved for their debug information instead, so there's no
+    // need for us to run dsymutil.
     if sess.target.target.options.is_like_osx
         && sess.opts.debuginfo != DebugInfo::None
-        && !preserve_objects_for_their_debuginfo(sess)
+        && sess.split_debuginfo() == SplitDebuginfo::Packed
     {
         if let Err(e) = Command::new("dsymutil").arg(out_filename).output() {
             sess.fatal(&format!("failed to run dsymutil: {}", e))
         }
     }
+
+    // On ELF targets the debug information split into `.dwo` files, both this
+    // crate's and those of its upstream crates, gets packed into a single
+    // `.dwp` file next to the output.
+    if sess.split_dwarf() && sess.split_debuginfo() == SplitDebuginfo::Packed {
+        link_dwarf_object(sess, out_filename);
+    }
+}
+
+fn escape_string(s: &[u8]) -> String {
+    str::from_utf8(s).map(|s| s.to_owned()).unwrap_or_else(|_| {
+        let mut x = "Non-UTF-8 output: ".to_string();
+        x.extend(s.iter().flat_map(|&b| ascii::escape_default(b)).map(char::from));
+        x
+    })
+}
+
+/// Runs `llvm-dwp` to pack the split DWARF objects referenced by the linked
+/// `executable_out_filename` into a `.dwp` file next to it.
+fn link_dwarf_object(sess: &Session, executable_out_filename: &Path) {
+    let dwp_out_filename = executable_out_filename.with_extension("dwp");
+    info!("preparing dwp to {:?}", dwp_out_filename);
+
+    let mut cmd = Command::new("llvm-dwp");
+    cmd.arg("-e");
+    cmd.arg(executable_out_filename);
+    cmd.arg("-o");
+    cmd.arg(&dwp_out_filename);
+
+    // Like the linker, `llvm-dwp` may be shipped in the compiler's sysroot.
+    let mut new_path = sess.host_filesearch(PathKind::All).get_tools_search_paths(false);
+    if let Some(path) = env::var_os("PATH") {
+        new_path.extend(env::split_paths(&path));
+    }
+    cmd.env("PATH", env::join_paths(new_path).unwrap());
+
+    info!("{:?}", &cmd);
+    match sess.time("run_dwp", || cmd.output()) {
+        Ok(prog) => {
+            if !prog.status.success() {
+                let mut output = prog.stderr.clone();
+                output.extend_from_slice(&prog.stdout);
+                sess.struct_err(&format!(
+                    "packing split debuginfo with `llvm-dwp` failed: {}",
+                    prog.status
+                ))
+                .note(&format!("{:?}", &cmd))
+                .note(&escape_string(&output))
+                .emit();
+            }
+            info!("llvm-dwp stderr:\n{}", escape_string(&prog.stderr));
+            info!("llvm-dwp stdout:\n{}", escape_string(&prog.stdout));
+        }
+        Err(e) => {
+            let dwp_not_found = e.kind() == io::ErrorKind::NotFound;
+
+            let mut err = if dwp_not_found {
+                sess.struct_err("`llvm-dwp` not found")
+            } else {
+                sess.struct_err("could not exec `llvm-dwp`")
+            };
+
+            err.note(&e.to_string());
+
+            if !dwp_not_found {
+                err.note(&format!("{:?}", &cmd));
+            } else {
+                err.note(
+                    "`-C split-debuginfo=packed` requires `llvm-dwp` to be installed, \
+                     or use `-C split-debuginfo=unpacked` to keep the `.dwo` files instead",
+                );
+            }
+
+            err.emit();
+        }
+    }
 }
 
 fn link_sanitizers(sess: &Session, crate_type: CrateType, linker: &mut dyn Linker) {
@@ -960,15 +1035,30 @@ fn preserve_objects_for_their_debuginfo(sess: &Session) -> bool {
     // that you don't do this sort of dwarf link).
     //
     // Basically as a result this just means that if we're on OSX and we're
-    // *not* running dsymutil then the object files are the only source of truth
-    // for debug information, so we must preserve them.
+    // *not* running dsymutil (`-C split-debuginfo=unpacked`) then the object
+    // files are the only source of truth for debug information, so we must
+    // preserve them.
     if sess.target.target.options.is_like_osx {
-        return !sess.opts.debugging_opts.run_dsymutil;
+        return sess.split_debuginfo() == SplitDebuginfo::Unpacked;
     }
 
     false
 }
 
+/// Returns `true` if the `.dwo` files produced for this crate's codegen units
+/// need to stay around after linking.
+fn preserve_dwarf_objects(sess: &Session) -> bool {
+    // With unpacked split debuginfo the linked output points at the `.dwo`
+    // files, they're the only source of truth for debug information.
+    if sess.split_debuginfo() != SplitDebuginfo::Packed {
+        return true;
+    }
+
+    // Rlibs and staticlibs don't contain the DWARF objects of their codegen
+    // units either, they're only packed when a downstream crate gets linked.
+    sess.crate_types().iter().any(|&x| x == CrateType::Rlib || x == CrateType::Staticlib)
+}
+
 pub fn archive_search_paths(sess: &Session) -> Vec<PathBuf> {
     sess.target_filesearch(PathKind::Native).search_path_dirs()
 }
@@ -274,10 +274,31 @@ impl ModuleConfig {
     }
 }
 
+/// Configuration passed to the function returned by the `target_machine_factory`.
+pub struct TargetMachineFactoryConfig {
+    /// Split DWARF is enabled in LLVM by setting the name of the DWARF object
+    /// file on the target machine, so it has to be known when creating it.
+    /// Backends which don't split debuginfo this way can ignore it.
+    pub split_dwarf_file: Option<PathBuf>,
+}
+
+impl TargetMachineFactoryConfig {
+    pub fn new(
+        cgcx: &CodegenContext<impl WriteBackendMethods>,
+        module_name: &str,
+    ) -> TargetMachineFactoryConfig {
+        TargetMachineFactoryConfig { split_dwarf_file: cgcx.split_dwarf_path(module_name) }
+    }
+}
+
+pub type TargetMachineFactoryFn<B> = Arc<
+    dyn Fn(TargetMachineFactoryConfig) -> Result<<B as WriteBackendMethods>::TargetMachine, String>
+        + Send
+        + Sync,
+>;
+
 // HACK(eddyb) work around `#[derive]` producing wrong bounds for `Clone`.
-pub struct TargetMachineFactory<B: WriteBackendMethods>(
-    pub Arc<dyn Fn() -> Result<B::TargetMachine, String> + Send + Sync>,
-);
+pub struct TargetMachineFactory<B: WriteBackendMethods>(pub TargetMachineFactoryFn<B>);
 
 impl<B: WriteBackendMethods> Clone for TargetMachineFactory<B> {
     fn clone(&self) -> Self {
@@ -310,6 +331,8 @@ pub struct CodegenContext<B: WriteBackendMethods> {
     pub target_pointer_width: String,
     pub target_arch: String,
     pub debuginfo: config::DebugInfo,
+    // Whether debuginfo is split out of object files into `.dwo` files
+    pub split_dwarf: bool,
 
     // Number of cgus excluding the allocator/metadata modules
     pub total_cgus: usize,
@@ -340,6 +363,12 @@ impl<B: WriteBackendMethods> CodegenContext<B> {
             ModuleKind::Allocator => &self.allocator_module_config,
         }
     }
+
+    /// Returns the path of the `.dwo` file that the debuginfo of the given
+    /// module is split into, if debuginfo is split at all.
+    pub fn split_dwarf_path(&self, module_name: &str) -> Option<PathBuf> {
+        self.split_dwarf.then(|| self.output_filenames.temp_path_dwo(Some(module_name)))
+    }
 }
 
 fn generate_lto_work<B: ExtraBackendMethods>(
@@ -488,10 +517,14 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
 
     for module in compiled_modules.modules.iter().filter(|m| m.kind == ModuleKind::Regular) {
         let path = module.object.as_ref().cloned();
-
-        if let Some((id, product)) =
-            copy_cgu_workproduct_to_incr_comp_cache_dir(sess, &module.name, &path)
-        {
+        let dwarf_object_path = module.dwarf_object.as_ref().cloned();
+
+        if let Some((id, product)) = copy_cgu_workproduct_to_incr_comp_cache_dir(
+            sess,
+            &module.name,
+            &path,
+            &dwarf_object_path,
+        ) {
             work_products.insert(id, product);
         }
     }
@@ -820,27 +853,38 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
     module_config: &ModuleConfig,
 ) -> Result<WorkItemResult<B>, FatalError> {
     let incr_comp_session_dir = cgcx.incr_comp_session_dir.as_ref().unwrap();
-    let mut object = None;
-    if let Some(saved_file) = module.source.saved_file {
-        let obj_out = cgcx.output_filenames.temp_path(OutputType::Object, Some(&module.name));
-        object = Some(obj_out.clone());
-        let source_file = in_incr_comp_dir(&incr_comp_session_dir, &saved_file);
+
+    let load_from_incr_comp_dir = |output_path: PathBuf, saved_path: &str| {
+        let source_file = in_incr_comp_dir(&incr_comp_session_dir, saved_path);
         debug!(
             "copying pre-existing module `{}` from {:?} to {}",
             module.name,
             source_file,
-            obj_out.display()
+            output_path.display()
         );
-        if let Err(err) = link_or_copy(&source_file, &obj_out) {
-            let diag_handler = cgcx.create_diag_handler();
-            diag_handler.err(&format!(
-                "unable to copy {} to {}: {}",
-                source_file.display(),
-                obj_out.display(),
-                err
-            ));
+        match link_or_copy(&source_file, &output_path) {
+            Ok(_) => Some(output_path),
+            Err(err) => {
+                let diag_handler = cgcx.create_diag_handler();
+                diag_handler.err(&format!(
+                    "unable to copy {} to {}: {}",
+                    source_file.display(),
+                    output_path.display(),
+                    err
+                ));
+                None
+            }
         }
-    }
+    };
+
+    let object = module.source.saved_file.as_ref().and_then(|saved_file| {
+        let output_path = cgcx.output_filenames.temp_path(OutputType::Object, Some(&module.name));
+        load_from_incr_comp_dir(output_path, &saved_file)
+    });
+    let dwarf_object = module.source.saved_dwarf_object.as_ref().and_then(|saved_dwarf_object| {
+        let output_path = cgcx.output_filenames.temp_path_dwo(Some(&module.name));
+        load_from_incr_comp_dir(output_path, &saved_dwarf_object)
+    });
 
     assert_eq!(object.is_some(), module_config.emit_obj != EmitObj::None);
 
@@ -848,6 +892,7 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
         name: module.name,
         kind: ModuleKind::Regular,
         object,
+        dwarf_object,
         bytecode: None,
     }))
 }
@@ -1025,6 +1070,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         target_pointer_width: tcx.sess.target.target.target_pointer_width.clone(),
         target_arch: tcx.sess.target.target.arch.clone(),
         debuginfo: tcx.sess.opts.debuginfo,
+        split_dwarf: tcx.sess.split_dwarf(),
     };
 
     // This is the "main loop" of parallel work happening for parallel codegen.
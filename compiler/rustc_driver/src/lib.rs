@@ -206,6 +206,7 @@ fn run_compiler(
 
     let sopts = config::build_session_options(&matches);
     let cfg = interface::parse_cfgspecs(matches.opt_strs("cfg"));
+    let check_cfg = interface::parse_check_cfg(matches.opt_strs("check-cfg"));
 
     // We wrap `make_codegen_backend` in another `Option` such that `dummy_config` can take
     // ownership of it when necessary, while also allowing the non-dummy config to take ownership
@@ -216,6 +217,7 @@ fn run_compiler(
         let mut config = interface::Config {
             opts: sopts,
             crate_cfg: cfg,
+            crate_check_cfg: Default::default(),
             input: Input::File(PathBuf::new()),
             input_path: None,
             output_file: None,
@@ -293,6 +295,7 @@ fn run_compiler(
     let mut config = interface::Config {
         opts: sopts,
         crate_cfg: cfg,
+        crate_check_cfg: check_cfg,
         input,
         input_path: input_file_path,
         output_file: ofile,
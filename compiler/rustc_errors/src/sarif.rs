@@ -0,0 +1,401 @@
+//! A SARIF emitter for errors.
+//!
+//! [SARIF] is the standard interchange format for the results of static analysis tools, ingested
+//! by code scanning and code review platforms. This emitter maps each diagnostic to a SARIF
+//! result: its primary spans become the result's locations, secondary spans and the spans of
+//! its children become related locations and suggestions become fixes.
+//!
+//! Unlike the JSON emitter, which prints every diagnostic as soon as it is emitted, a SARIF log
+//! is a single document describing the whole run. The results are therefore collected as the
+//! diagnostics are emitted and the log is written out when the emitter is dropped.
+//!
+//! [SARIF]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use rustc_span::source_map::SourceMap;
+
+use crate::emitter::Emitter;
+use crate::{CodeSuggestion, DiagnosticId, Level, SubDiagnostic};
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_serialize::json::{Json, ToJson};
+use rustc_span::{FileName, Span};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::thread;
+
+#[cfg(test)]
+mod tests;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    sm: Lrc<SourceMap>,
+    /// The rules (error codes and lints) referenced by the results, in order of appearance.
+    rules: FxIndexMap<String, Rule>,
+    results: Vec<SarifResult>,
+}
+
+impl SarifEmitter {
+    pub fn stderr(source_map: Lrc<SourceMap>) -> SarifEmitter {
+        SarifEmitter::new(Box::new(io::BufWriter::new(io::stderr())), source_map)
+    }
+
+    pub fn new(dst: Box<dyn Write + Send>, source_map: Lrc<SourceMap>) -> SarifEmitter {
+        SarifEmitter { dst, sm: source_map, rules: FxIndexMap::default(), results: vec![] }
+    }
+
+    fn log(&self) -> Json {
+        let driver = object(vec![
+            ("name", "rustc".to_json()),
+            ("version", option_env!("CFG_VERSION").unwrap_or("unknown").to_json()),
+            ("informationUri", "https://www.rust-lang.org/".to_json()),
+            ("rules", self.rules.values().collect::<Vec<_>>().to_json()),
+        ]);
+        let run = object(vec![
+            ("tool", object(vec![("driver", driver)])),
+            // Columns are counted the way rustc counts them in its other outputs.
+            ("columnKind", "unicodeCodePoints".to_json()),
+            ("results", self.results.to_json()),
+        ]);
+        object(vec![
+            ("$schema", SARIF_SCHEMA.to_json()),
+            ("version", SARIF_VERSION.to_json()),
+            ("runs", Json::Array(vec![run])),
+        ])
+    }
+
+    fn rule_index(&mut self, code: &DiagnosticId) -> (String, usize) {
+        let (id, help_uri) = match code {
+            DiagnosticId::Error(code) => {
+                (code.clone(), Some(format!("https://doc.rust-lang.org/error-index.html#{}", code)))
+            }
+            DiagnosticId::Lint { name, .. } => (name.clone(), None),
+        };
+        let entry = self.rules.entry(id.clone());
+        let index = entry.index();
+        entry.or_insert_with(|| Rule { id: id.clone(), help_uri });
+        (id, index)
+    }
+
+    /// Converts the span into a SARIF location, or `None` if it doesn't point into a file.
+    fn location(&self, span: Span, message: Option<String>) -> Option<Location> {
+        if span.is_dummy() {
+            return None;
+        }
+        // Code generated by macros from other crates can't be pointed at, so use the place the
+        // macro was invoked from instead.
+        let span = if self.sm.lookup_char_pos(span.lo()).file.is_real_file() {
+            span
+        } else {
+            span.source_callsite()
+        };
+        let start = self.sm.lookup_char_pos(span.lo());
+        if !start.file.is_real_file() {
+            return None;
+        }
+        let end = self.sm.lookup_char_pos(span.hi());
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        Some(Location {
+            uri: file_uri(&start.file.name),
+            region: Region {
+                start_line: start.line,
+                start_column: start.col.0 + 1,
+                end_line: end.line,
+                end_column: end.col.0 + 1,
+                byte_offset: byte_start,
+                byte_length: byte_end - byte_start,
+                snippet: self.sm.span_to_snippet(span).ok(),
+            },
+            message,
+        })
+    }
+
+    fn fixes(&self, suggestion: &CodeSuggestion) -> Vec<Fix> {
+        suggestion
+            .substitutions
+            .iter()
+            .filter_map(|substitution| {
+                let mut changes: FxIndexMap<String, Vec<Replacement>> = FxIndexMap::default();
+                for part in &substitution.parts {
+                    let location = self.location(part.span, None)?;
+                    changes.entry(location.uri).or_default().push(Replacement {
+                        deleted_region: location.region,
+                        inserted_content: part.snippet.clone(),
+                    });
+                }
+                Some(Fix {
+                    description: suggestion.msg.clone(),
+                    applicability: format!("{:?}", suggestion.applicability),
+                    changes: changes.into_iter().collect(),
+                })
+            })
+            .collect()
+    }
+
+    fn result(&mut self, diag: &crate::Diagnostic) -> Option<SarifResult> {
+        let level = match diag.level {
+            Level::Bug | Level::Fatal | Level::Error => "error",
+            Level::Warning => "warning",
+            Level::Note | Level::Help => "note",
+            // Messages like "aborting due to previous error" only make sense in a terminal.
+            Level::Cancelled | Level::FailureNote | Level::Allow => return None,
+        };
+        let rule = diag.code.as_ref().map(|code| self.rule_index(code));
+
+        let mut message = diag.message();
+        let mut locations = vec![];
+        let mut related_locations = vec![];
+        for label in diag.span.span_labels() {
+            if label.is_primary {
+                locations.extend(self.location(label.span, label.label));
+            } else {
+                related_locations.extend(self.location(label.span, label.label));
+            }
+        }
+
+        // Children pointing at code become related locations, the others can only be part of the
+        // result's message.
+        for child in &diag.children {
+            let child_message = sub_diagnostic_message(child);
+            if child.span.primary_spans().is_empty() {
+                message.push('\n');
+                message.push_str(&child_message);
+            } else {
+                for label in child.span.span_labels() {
+                    let message = match label.label {
+                        Some(label) if !label.is_empty() => format!("{}: {}", child_message, label),
+                        _ => child_message.clone(),
+                    };
+                    related_locations.extend(self.location(label.span, Some(message)));
+                }
+            }
+        }
+
+        let fixes = diag.suggestions.iter().flat_map(|sugg| self.fixes(sugg)).collect();
+
+        Some(SarifResult { rule, level, message, locations, related_locations, fixes })
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
+        if let Some(result) = self.result(diag) {
+            self.results.push(result);
+        }
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let log = self.log();
+        let result = writeln!(&mut self.dst, "{}", log).and_then(|_| self.dst.flush());
+        if let Err(e) = result {
+            // Don't turn a panic, e.g. a fatal error, into an abort.
+            if !thread::panicking() {
+                panic!("failed to print diagnostics: {:?}", e);
+            }
+        }
+    }
+}
+
+// The following data types are provided just for serialisation.
+
+struct Rule {
+    id: String,
+    help_uri: Option<String>,
+}
+
+struct SarifResult {
+    /// The error code or lint name, and its index in the rules of the run.
+    rule: Option<(String, usize)>,
+    /// "error", "warning" or "note".
+    level: &'static str,
+    message: String,
+    locations: Vec<Location>,
+    related_locations: Vec<Location>,
+    fixes: Vec<Fix>,
+}
+
+struct Location {
+    uri: String,
+    region: Region,
+    message: Option<String>,
+}
+
+struct Region {
+    /// 1-based.
+    start_line: usize,
+    end_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_column: usize,
+    byte_offset: u32,
+    byte_length: u32,
+    /// The source text of the region.
+    snippet: Option<String>,
+}
+
+struct Fix {
+    description: String,
+    applicability: String,
+    /// The replacements to make, grouped by the URI of the file they apply to.
+    changes: Vec<(String, Vec<Replacement>)>,
+}
+
+struct Replacement {
+    deleted_region: Region,
+    inserted_content: String,
+}
+
+impl ToJson for Rule {
+    fn to_json(&self) -> Json {
+        let mut fields = vec![("id", self.id.to_json())];
+        if let Some(ref help_uri) = self.help_uri {
+            fields.push(("helpUri", help_uri.to_json()));
+        }
+        object(fields)
+    }
+}
+
+impl ToJson for SarifResult {
+    fn to_json(&self) -> Json {
+        let mut fields = vec![("level", self.level.to_json()), ("message", text(&self.message))];
+        if let Some((ref id, index)) = self.rule {
+            fields.push(("ruleId", id.to_json()));
+            fields.push(("ruleIndex", index.to_json()));
+        }
+        fields.push(("locations", self.locations.to_json()));
+        if !self.related_locations.is_empty() {
+            let related_locations = self
+                .related_locations
+                .iter()
+                .enumerate()
+                .map(|(id, location)| match location.to_json() {
+                    Json::Object(mut location) => {
+                        location.insert("id".to_string(), id.to_json());
+                        Json::Object(location)
+                    }
+                    _ => unreachable!(),
+                })
+                .collect();
+            fields.push(("relatedLocations", Json::Array(related_locations)));
+        }
+        if !self.fixes.is_empty() {
+            fields.push(("fixes", self.fixes.to_json()));
+        }
+        object(fields)
+    }
+}
+
+impl ToJson for Location {
+    fn to_json(&self) -> Json {
+        let physical_location = object(vec![
+            ("artifactLocation", object(vec![("uri", self.uri.to_json())])),
+            ("region", self.region.to_json()),
+        ]);
+        let mut fields = vec![("physicalLocation", physical_location)];
+        if let Some(ref message) = self.message {
+            fields.push(("message", text(message)));
+        }
+        object(fields)
+    }
+}
+
+impl ToJson for Region {
+    fn to_json(&self) -> Json {
+        let mut fields = vec![
+            ("startLine", self.start_line.to_json()),
+            ("startColumn", self.start_column.to_json()),
+            ("endLine", self.end_line.to_json()),
+            ("endColumn", self.end_column.to_json()),
+            ("byteOffset", self.byte_offset.to_json()),
+            ("byteLength", self.byte_length.to_json()),
+        ];
+        if let Some(ref snippet) = self.snippet {
+            fields.push(("snippet", text(snippet)));
+        }
+        object(fields)
+    }
+}
+
+impl ToJson for Fix {
+    fn to_json(&self) -> Json {
+        let artifact_changes = self
+            .changes
+            .iter()
+            .map(|(uri, replacements)| {
+                object(vec![
+                    ("artifactLocation", object(vec![("uri", uri.to_json())])),
+                    ("replacements", replacements.to_json()),
+                ])
+            })
+            .collect();
+        object(vec![
+            ("description", text(&self.description)),
+            ("artifactChanges", Json::Array(artifact_changes)),
+            ("properties", object(vec![("applicability", self.applicability.to_json())])),
+        ])
+    }
+}
+
+impl ToJson for Replacement {
+    fn to_json(&self) -> Json {
+        // Only the byte offsets identify the replaced text, the snippet would be misleading.
+        let deleted_region = object(vec![
+            ("byteOffset", self.deleted_region.byte_offset.to_json()),
+            ("byteLength", self.deleted_region.byte_length.to_json()),
+        ]);
+        object(vec![
+            ("deletedRegion", deleted_region),
+            ("insertedContent", text(&self.inserted_content)),
+        ])
+    }
+}
+
+fn object(fields: Vec<(&str, Json)>) -> Json {
+    Json::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect::<BTreeMap<_, _>>())
+}
+
+/// A SARIF message object with plain text.
+fn text(s: &str) -> Json {
+    object(vec![("text", s.to_json())])
+}
+
+fn sub_diagnostic_message(diag: &SubDiagnostic) -> String {
+    format!("{}: {}", diag.level.to_str(), diag.message())
+}
+
+/// Turns the file name into a URI reference. Relative paths stay relative, which SARIF consumers
+/// resolve against the root of the analyzed sources.
+fn file_uri(name: &FileName) -> String {
+    let path = name.to_string().replace('\\', "/");
+    let mut uri = String::with_capacity(path.len());
+    if path.starts_with('/') {
+        uri.push_str("file://");
+    } else if path.as_bytes().get(1) == Some(&b':') {
+        // An absolute path with a drive letter on Windows.
+        uri.push_str("file:///");
+    }
+    for c in path.chars() {
+        match c {
+            ' ' => uri.push_str("%20"),
+            '#' => uri.push_str("%23"),
+            '%' => uri.push_str("%25"),
+            '?' => uri.push_str("%3F"),
+            c => uri.push(c),
+        }
+    }
+    uri
+}
@@ -0,0 +1,158 @@
+use super::*;
+
+use crate::{Applicability, Handler};
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::BytePos;
+
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+fn with_default_session_globals(f: impl FnOnce()) {
+    let session_globals = rustc_span::SessionGlobals::new(rustc_span::edition::DEFAULT_EDITION);
+    rustc_span::SESSION_GLOBALS.set(&session_globals, f);
+}
+
+/// Emits the diagnostics with a SARIF emitter for a `test.rs` file containing `code`, and returns
+/// the single run of the resulting log.
+fn test_sarif(code: &str, emit: impl FnOnce(&Handler)) -> Json {
+    let output = Arc::new(Mutex::new(Vec::new()));
+
+    with_default_session_globals(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+
+        let emitter = SarifEmitter::new(Box::new(Shared { data: output.clone() }), sm);
+        let handler = Handler::with_emitter(true, None, Box::new(emitter));
+        emit(&handler);
+    });
+
+    let bytes = output.lock().unwrap();
+    let log = Json::from_str(str::from_utf8(&bytes).unwrap()).unwrap();
+    assert_eq!(log.find("version"), Some(&Json::String("2.1.0".to_string())));
+    let runs = log.find("runs").and_then(|runs| runs.as_array()).unwrap();
+    assert_eq!(runs.len(), 1);
+    runs[0].clone()
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+fn string(json: &Json, path: &[&str]) -> String {
+    json.find_path(path).and_then(|s| s.as_string()).unwrap().to_string()
+}
+
+fn number(json: &Json, path: &[&str]) -> u64 {
+    json.find_path(path).and_then(|n| n.as_u64()).unwrap()
+}
+
+#[test]
+fn primary_and_secondary_spans() {
+    let run = test_sarif("fn foo() {}\nfn foo() {}\n", |handler| {
+        let mut err = handler.struct_span_err(span(15, 18), "the name `foo` is defined twice");
+        err.code(DiagnosticId::Error("E0428".to_string()));
+        err.span_label(span(15, 18), "`foo` redefined here");
+        err.span_label(span(3, 6), "previous definition here");
+        err.note("`foo` must be defined only once");
+        err.emit();
+    });
+
+    let results = run.find("results").and_then(|r| r.as_array()).unwrap();
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(string(result, &["level"]), "error");
+    assert_eq!(string(result, &["ruleId"]), "E0428");
+    assert_eq!(
+        string(result, &["message", "text"]),
+        "the name `foo` is defined twice\nnote: `foo` must be defined only once"
+    );
+
+    let location = &result.find("locations").and_then(|l| l.as_array()).unwrap()[0];
+    let physical_location = location.find("physicalLocation").unwrap();
+    assert_eq!(string(physical_location, &["artifactLocation", "uri"]), "test.rs");
+    assert_eq!(number(physical_location, &["region", "startLine"]), 2);
+    assert_eq!(number(physical_location, &["region", "startColumn"]), 4);
+    assert_eq!(number(physical_location, &["region", "endColumn"]), 7);
+    assert_eq!(number(physical_location, &["region", "byteOffset"]), 15);
+    assert_eq!(number(physical_location, &["region", "byteLength"]), 3);
+    assert_eq!(string(location, &["message", "text"]), "`foo` redefined here");
+
+    let related = &result.find("relatedLocations").and_then(|l| l.as_array()).unwrap()[0];
+    assert_eq!(number(related, &["physicalLocation", "region", "startLine"]), 1);
+    assert_eq!(string(related, &["message", "text"]), "previous definition here");
+
+    let rule = &run.find_path(&["tool", "driver", "rules"]).and_then(|r| r.as_array()).unwrap()[0];
+    assert_eq!(string(rule, &["id"]), "E0428");
+    assert_eq!(number(result, &["ruleIndex"]), 0);
+}
+
+#[test]
+fn suggestions_become_fixes() {
+    let run = test_sarif("let x = 1;\n", |handler| {
+        let mut err = handler.struct_span_warn(span(4, 5), "unused variable: `x`");
+        err.code(DiagnosticId::Lint {
+            name: "unused_variables".to_string(),
+            has_future_breakage: false,
+        });
+        err.span_suggestion(
+            span(4, 5),
+            "if this is intentional, prefix it with an underscore",
+            "_x".to_string(),
+            Applicability::MachineApplicable,
+        );
+        err.emit();
+    });
+
+    let result = &run.find("results").and_then(|r| r.as_array()).unwrap()[0];
+    assert_eq!(string(result, &["level"]), "warning");
+    assert_eq!(string(result, &["ruleId"]), "unused_variables");
+
+    let fix = &result.find("fixes").and_then(|f| f.as_array()).unwrap()[0];
+    assert_eq!(
+        string(fix, &["description", "text"]),
+        "if this is intentional, prefix it with an underscore"
+    );
+    assert_eq!(string(fix, &["properties", "applicability"]), "MachineApplicable");
+    let change = &fix.find("artifactChanges").and_then(|c| c.as_array()).unwrap()[0];
+    assert_eq!(string(change, &["artifactLocation", "uri"]), "test.rs");
+    let replacement = &change.find("replacements").and_then(|r| r.as_array()).unwrap()[0];
+    assert_eq!(number(replacement, &["deletedRegion", "byteOffset"]), 4);
+    assert_eq!(number(replacement, &["deletedRegion", "byteLength"]), 1);
+    assert_eq!(string(replacement, &["insertedContent", "text"]), "_x");
+}
+
+#[test]
+fn failure_notes_are_skipped() {
+    let run = test_sarif("", |handler| {
+        handler.struct_err("aborting due to previous error").emit();
+        handler.failure("For more information about this error, try `rustc --explain E0428`.");
+    });
+
+    let results = run.find("results").and_then(|r| r.as_array()).unwrap();
+    assert_eq!(results.len(), 1);
+    assert!(results[0].find("locations").and_then(|l| l.as_array()).unwrap().is_empty());
+}
+
+#[test]
+fn file_uris() {
+    assert_eq!(file_uri(&FileName::from(Path::new("src/main.rs").to_owned())), "src/main.rs");
+    assert_eq!(
+        file_uri(&FileName::from(Path::new("/home/my crate/src/#lib.rs").to_owned())),
+        "file:///home/my%20crate/src/%23lib.rs"
+    );
+}
@@ -602,6 +602,21 @@ declare_features! (
     /// Allows `#[instruction_set(_)]` attribute
     (active, isa_attribute, "1.48.0", Some(74727), None),
 
+    /// Allows `#[test_timeout(secs)]` to set a hard time limit on a single test.
+    (active, test_timeout, "1.49.0", None, None),
+
+    /// Allows capturing disjoint fields in a closure/generator (RFC 2229).
+    (active, capture_disjoint_fields, "1.49.0", Some(53488), None),
+
+    /// Allows `let...else` statements.
+    (active, let_else, "1.49.0", Some(87335), None),
+
+    /// Allows destructuring assignments, e.g. `(a, b) = (b, a)`.
+    (active, destructuring_assignment, "1.49.0", Some(71126), None),
+
+    /// Allows default values for const generic parameters, e.g. `struct Foo<const N: usize = 3>`.
+    (active, const_generics_defaults, "1.49.0", Some(44580), None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -622,6 +637,7 @@ pub const INCOMPLETE_FEATURES: &[Symbol] = &[
     sym::const_trait_bound_opt_out,
     sym::lazy_normalization_consts,
     sym::specialization,
+    sym::capture_disjoint_fields,
 ];
 
 /// Some features are not allowed to be used together at the same time, if
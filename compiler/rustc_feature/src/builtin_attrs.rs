@@ -320,6 +320,7 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
 
     // Testing:
     gated!(allow_fail, Normal, template!(Word), experimental!(allow_fail)),
+    gated!(test_timeout, Normal, template!(List: "seconds"), experimental!(test_timeout)),
     gated!(
         test_runner, CrateLevel, template!(List: "path"), custom_test_frameworks,
         "custom test frameworks are an unstable feature",
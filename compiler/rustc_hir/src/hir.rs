@@ -418,6 +418,8 @@ pub enum GenericParamKind<'hir> {
     },
     Const {
         ty: &'hir Ty<'hir>,
+        /// Optional default value for the const generic param
+        default: Option<AnonConst>,
     },
 }
 
@@ -1709,6 +1711,9 @@ pub enum LocalSource {
     AsyncFn,
     /// A desugared `<expr>.await`.
     AwaitDesugar,
+    /// A desugared expression statement `<lhs> = <rhs>;`, where `<lhs>` is a destructuring
+    /// pattern. The span is that of the `=` sign.
+    AssignDesugar(Span),
 }
 
 /// Hints at the original code for a `match _ { .. }`.
@@ -1732,6 +1737,9 @@ pub enum MatchSource {
     TryDesugar,
     /// A desugared `<expr>.await`.
     AwaitDesugar,
+    /// A `let _ = _ else { .. }` (which was desugared to a `match` whose first arm contains
+    /// the rest of the enclosing block).
+    LetElseDesugar,
 }
 
 impl MatchSource {
@@ -1744,6 +1752,7 @@ impl MatchSource {
             ForLoopDesugar => "for",
             TryDesugar => "?",
             AwaitDesugar => ".await",
+            LetElseDesugar => "let...else",
         }
     }
 }
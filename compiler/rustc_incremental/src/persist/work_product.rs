@@ -11,37 +11,53 @@ pub fn copy_cgu_workproduct_to_incr_comp_cache_dir(
     sess: &Session,
     cgu_name: &str,
     path: &Option<PathBuf>,
+    dwarf_object_path: &Option<PathBuf>,
 ) -> Option<(WorkProductId, WorkProduct)> {
-    debug!("copy_cgu_workproduct_to_incr_comp_cache_dir({:?},{:?})", cgu_name, path);
+    debug!(
+        "copy_cgu_workproduct_to_incr_comp_cache_dir({:?},{:?},{:?})",
+        cgu_name, path, dwarf_object_path
+    );
     sess.opts.incremental.as_ref()?;
 
-    let saved_file = if let Some(path) = path {
-        let file_name = format!("{}.o", cgu_name);
-        let path_in_incr_dir = in_incr_comp_dir_sess(sess, &file_name);
-        match link_or_copy(path, &path_in_incr_dir) {
-            Ok(_) => Some(file_name),
-            Err(err) => {
-                sess.warn(&format!(
-                    "error copying object file `{}` to incremental directory as `{}`: {}",
-                    path.display(),
-                    path_in_incr_dir.display(),
-                    err
-                ));
-                return None;
-            }
-        }
-    } else {
-        None
-    };
+    let saved_file = copy_file_to_incr_comp_dir(sess, path, &format!("{}.o", cgu_name))?;
+    let saved_dwarf_object =
+        copy_file_to_incr_comp_dir(sess, dwarf_object_path, &format!("{}.dwo", cgu_name))?;
 
-    let work_product = WorkProduct { cgu_name: cgu_name.to_string(), saved_file };
+    let work_product =
+        WorkProduct { cgu_name: cgu_name.to_string(), saved_file, saved_dwarf_object };
 
     let work_product_id = WorkProductId::from_cgu_name(cgu_name);
     Some((work_product_id, work_product))
 }
 
+/// Copies `path`, if any, into the incremental directory as `file_name`. Returns `None` if
+/// copying failed, in which case the work product can't be saved.
+fn copy_file_to_incr_comp_dir(
+    sess: &Session,
+    path: &Option<PathBuf>,
+    file_name: &str,
+) -> Option<Option<String>> {
+    let path = match path {
+        Some(path) => path,
+        None => return Some(None),
+    };
+    let path_in_incr_dir = in_incr_comp_dir_sess(sess, file_name);
+    match link_or_copy(path, &path_in_incr_dir) {
+        Ok(_) => Some(Some(file_name.to_string())),
+        Err(err) => {
+            sess.warn(&format!(
+                "error copying object file `{}` to incremental directory as `{}`: {}",
+                path.display(),
+                path_in_incr_dir.display(),
+                err
+            ));
+            None
+        }
+    }
+}
+
 pub fn delete_workproduct_files(sess: &Session, work_product: &WorkProduct) {
-    if let Some(ref file_name) = work_product.saved_file {
+    for file_name in work_product.saved_files() {
         let path = in_incr_comp_dir_sess(sess, file_name);
         match std_fs::remove_file(&path) {
             Ok(()) => {}
@@ -888,36 +888,32 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         def_id: DefId,
         substs: ty::subst::SubstsRef<'tcx>,
     ) -> SubstsRef<'tcx> {
+        use ty::subst::GenericArg;
+
         let generics = self.tcx.generics_of(def_id);
-        let mut num_supplied_defaults = 0;
-        let mut type_params = generics
+        let num_supplied_defaults = generics
             .params
             .iter()
             .rev()
-            .filter_map(|param| match param.kind {
-                ty::GenericParamDefKind::Lifetime => None,
-                ty::GenericParamDefKind::Type { has_default, .. } => {
-                    Some((param.def_id, has_default))
-                }
-                ty::GenericParamDefKind::Const => None, // FIXME(const_generics:defaults)
+            .take_while(|param| {
+                let default = match param.kind {
+                    ty::GenericParamDefKind::Lifetime => return false,
+                    ty::GenericParamDefKind::Type { has_default, .. } => {
+                        if !has_default {
+                            return false;
+                        }
+                        GenericArg::from(self.tcx.type_of(param.def_id).subst(self.tcx, substs))
+                    }
+                    ty::GenericParamDefKind::Const { has_default } => {
+                        if !has_default {
+                            return false;
+                        }
+                        GenericArg::from(self.tcx.const_param_default(param.def_id))
+                    }
+                };
+                substs.get(param.index as usize) == Some(&default)
             })
-            .peekable();
-        let has_default = {
-            let has_default = type_params.peek().map(|(_, has_default)| has_default);
-            *has_default.unwrap_or(&false)
-        };
-        if has_default {
-            let types = substs.types().rev();
-            for ((def_id, has_default), actual) in type_params.zip(types) {
-                if !has_default {
-                    break;
-                }
-                if self.tcx.type_of(def_id).subst(self.tcx, substs) != actual {
-                    break;
-                }
-                num_supplied_defaults += 1;
-            }
-        }
+            .count();
         let len = generics.params.len();
         let mut generics = generics.clone();
         generics.params.truncate(len - num_supplied_defaults);
@@ -1098,8 +1094,8 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
                     let len1 = sub_no_defaults_1.len();
                     let len2 = sub_no_defaults_2.len();
                     let common_len = cmp::min(len1, len2);
-                    let remainder1: Vec<_> = sub1.types().skip(common_len).collect();
-                    let remainder2: Vec<_> = sub2.types().skip(common_len).collect();
+                    let remainder1: Vec<_> = sub1.iter().skip(common_len).collect();
+                    let remainder2: Vec<_> = sub2.iter().skip(common_len).collect();
                     let common_default_params = remainder1
                         .iter()
                         .rev()
@@ -1107,7 +1103,11 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
                         .filter(|(a, b)| a == b)
                         .count();
                     let len = sub1.len() - common_default_params;
-                    let consts_offset = len - sub1.consts().count();
+                    // The elided default parameters may be types or consts, and the consts come
+                    // after the types.
+                    let regions_len = sub1.regions().count();
+                    let num_display_types = cmp::min(sub1.types().count(), len - regions_len);
+                    let consts_offset = regions_len + num_display_types;
 
                     // Only draw `<...>` if there're lifetime/type arguments.
                     if len > 0 {
@@ -1149,8 +1149,6 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
                     //     Foo<_, Qux>
                     //         ^ elided type as this type argument was the same in both sides
                     let type_arguments = sub1.types().zip(sub2.types());
-                    let regions_len = sub1.regions().count();
-                    let num_display_types = consts_offset - regions_len;
                     for (i, (ta1, ta2)) in type_arguments.take(num_display_types).enumerate() {
                         let i = i + regions_len;
                         if ta1 == ta2 {
@@ -1167,7 +1165,7 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
                     // Do the same for const arguments, if they are equal, do not highlight and
                     // elide them from the output.
                     let const_arguments = sub1.consts().zip(sub2.consts());
-                    for (i, (ca1, ca2)) in const_arguments.enumerate() {
+                    for (i, (ca1, ca2)) in const_arguments.take(len - consts_offset).enumerate() {
                         let i = i + consts_offset;
                         if ca1 == ca2 {
                             values.0.push_normal("_");
@@ -52,6 +52,7 @@ mod levels;
 mod non_ascii_idents;
 mod nonstandard_style;
 mod passes;
+mod prelude_collisions;
 mod redundant_semicolon;
 mod traits;
 mod types;
@@ -75,6 +76,7 @@ use builtin::*;
 use internal::*;
 use non_ascii_idents::*;
 use nonstandard_style::*;
+use prelude_collisions::PreludeCollisions;
 use redundant_semicolon::*;
 use traits::*;
 use types::*;
@@ -158,6 +160,7 @@ macro_rules! late_lint_passes {
                 // and change this to a module lint pass
                 MissingDebugImplementations: MissingDebugImplementations::default(),
                 ArrayIntoIter: ArrayIntoIter,
+                PreludeCollisions: PreludeCollisions,
                 ClashingExternDeclarations: ClashingExternDeclarations::new(),
                 DropTraitConstraints: DropTraitConstraints,
             ]
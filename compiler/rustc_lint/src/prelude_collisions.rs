@@ -0,0 +1,239 @@
+use crate::{LateContext, LateLintPass, LintContext};
+use rustc_errors::Applicability;
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_middle::ty::adjustment::{Adjust, AutoBorrow, AutoBorrowMutability};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_session::lint::FutureIncompatibleInfo;
+use rustc_span::edition::Edition;
+use rustc_span::symbol::{sym, Symbol};
+
+declare_lint! {
+    /// The `rust_2021_prelude_collisions` lint detects the usage of trait
+    /// methods which are ambiguous with traits added to the prelude in the
+    /// 2021 edition.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![deny(rust_2021_prelude_collisions)]
+    ///
+    /// trait Foo {
+    ///     fn try_into(self) -> Result<String, ()>;
+    /// }
+    ///
+    /// impl Foo for &str {
+    ///     fn try_into(self) -> Result<String, ()> {
+    ///         Ok(String::from(self))
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let x: String = "3".try_into().unwrap();
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// The 2021 edition adds `TryFrom`, `TryInto` and `FromIterator` to the
+    /// prelude. A call to a method or associated function with the same name
+    /// as one of their items, such as `x.try_into()` or `T::from_iter(iter)`,
+    /// that currently resolves to a different trait will become ambiguous
+    /// once those traits are in scope. Calling the function through its fully
+    /// qualified path avoids the ambiguity in every edition.
+    pub RUST_2021_PRELUDE_COLLISIONS,
+    Allow,
+    "detects the usage of trait methods which are ambiguous with traits added to the \
+        prelude in the 2021 edition",
+    @future_incompatible = FutureIncompatibleInfo {
+        reference: "issue #85684 <https://github.com/rust-lang/rust/issues/85684>",
+        edition: Some(Edition::Edition2021),
+    };
+}
+
+declare_lint_pass!(
+    /// Checks for calls that become ambiguous with the 2021 prelude.
+    PreludeCollisions => [RUST_2021_PRELUDE_COLLISIONS]
+);
+
+/// Returns the diagnostic name of the 2021 prelude trait that provides an item called `name`.
+fn prelude_2021_trait(name: Symbol) -> Option<Symbol> {
+    match name {
+        sym::try_into => Some(sym::TryInto),
+        sym::try_from => Some(sym::TryFrom),
+        sym::from_iter => Some(sym::FromIterator),
+        _ => None,
+    }
+}
+
+/// Returns whether `snippet` is wrapped in a single pair of parentheses, like `(a + b)` but
+/// unlike `(a) + (b)`.
+fn is_parenthesized(snippet: &str) -> bool {
+    if !snippet.starts_with('(') {
+        return false;
+    }
+    let mut depth = 0usize;
+    for (i, c) in snippet.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i == snippet.len() - 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+impl<'tcx> LateLintPass<'tcx> for PreludeCollisions {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        // Code written for the 2021 edition already sees the new prelude.
+        if expr.span.rust_2021() {
+            return;
+        }
+
+        match &expr.kind {
+            hir::ExprKind::MethodCall(segment, _, args, _) => {
+                let prelude_trait = match prelude_2021_trait(segment.ident.name) {
+                    Some(prelude_trait) => prelude_trait,
+                    None => return,
+                };
+                let def_id = match cx.typeck_results().type_dependent_def_id(expr.hir_id) {
+                    Some(def_id) => def_id,
+                    None => return,
+                };
+                // Inherent methods take precedence over any trait in scope.
+                let trait_id = match cx.tcx.trait_of_item(def_id) {
+                    Some(trait_id) => trait_id,
+                    None => return,
+                };
+                if cx.tcx.is_diagnostic_item(prelude_trait, trait_id) {
+                    return;
+                }
+
+                cx.struct_span_lint(RUST_2021_PRELUDE_COLLISIONS, expr.span, |lint| {
+                    let mut lint = lint.build(&format!(
+                        "trait method `{}` will become ambiguous in Rust 2021",
+                        segment.ident.name
+                    ));
+
+                    // Rebuild the receiver the way method lookup adjusted it, so that the
+                    // fully qualified call passes exactly the same value.
+                    let source_map = cx.sess().source_map();
+                    let mut receiver = String::new();
+                    for adjustment in cx.typeck_results().expr_adjustments(&args[0]) {
+                        match adjustment.kind {
+                            Adjust::Deref(_) => receiver.push('*'),
+                            Adjust::Borrow(AutoBorrow::Ref(_, AutoBorrowMutability::Not)) => {
+                                receiver.insert(0, '&')
+                            }
+                            Adjust::Borrow(AutoBorrow::Ref(
+                                _,
+                                AutoBorrowMutability::Mut { .. },
+                            )) => receiver.insert_str(0, "&mut "),
+                            _ => {}
+                        }
+                    }
+                    let snippets = args
+                        .iter()
+                        .map(|arg| source_map.span_to_snippet(arg.span))
+                        .collect::<Result<Vec<_>, _>>();
+
+                    match snippets {
+                        Ok(snippets) if segment.args.is_none() => {
+                            // The adjustments bind tighter than most expressions, so the
+                            // receiver keeps its meaning only if it is parenthesized.
+                            let needs_parens = !receiver.is_empty()
+                                && !is_parenthesized(&snippets[0])
+                                && !matches!(
+                                    args[0].kind,
+                                    hir::ExprKind::Path(..)
+                                        | hir::ExprKind::Field(..)
+                                        | hir::ExprKind::Call(..)
+                                        | hir::ExprKind::MethodCall(..)
+                                        | hir::ExprKind::Lit(..)
+                                );
+                            if needs_parens {
+                                receiver.push_str(&format!("({})", snippets[0]));
+                            } else {
+                                receiver.push_str(&snippets[0]);
+                            }
+                            let args = std::iter::once(receiver)
+                                .chain(snippets[1..].iter().cloned())
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            let trait_path =
+                                with_no_trimmed_paths(|| cx.tcx.def_path_str(trait_id));
+                            lint.span_suggestion(
+                                expr.span,
+                                "disambiguate the method call",
+                                format!("{}::{}({})", trait_path, segment.ident, args),
+                                Applicability::MachineApplicable,
+                            );
+                        }
+                        _ => {
+                            lint.help(&format!(
+                                "call `{}` through the fully qualified path of its trait",
+                                segment.ident
+                            ));
+                        }
+                    }
+                    lint.emit();
+                });
+            }
+            hir::ExprKind::Path(qpath) => {
+                let (self_ty, segment) = match qpath {
+                    hir::QPath::TypeRelative(self_ty, segment) => (self_ty, segment),
+                    _ => return,
+                };
+                let prelude_trait = match prelude_2021_trait(segment.ident.name) {
+                    Some(prelude_trait) => prelude_trait,
+                    None => return,
+                };
+                let def_id = match cx.typeck_results().qpath_res(qpath, expr.hir_id) {
+                    Res::Def(DefKind::AssocFn, def_id) => def_id,
+                    _ => return,
+                };
+                let trait_id = match cx.tcx.trait_of_item(def_id) {
+                    Some(trait_id) => trait_id,
+                    None => return,
+                };
+                if cx.tcx.is_diagnostic_item(prelude_trait, trait_id) {
+                    return;
+                }
+
+                cx.struct_span_lint(RUST_2021_PRELUDE_COLLISIONS, expr.span, |lint| {
+                    let mut lint = lint.build(&format!(
+                        "trait-associated function `{}` will become ambiguous in Rust 2021",
+                        segment.ident.name
+                    ));
+                    match cx.sess().source_map().span_to_snippet(self_ty.span) {
+                        Ok(self_ty) if segment.args.is_none() => {
+                            let trait_path =
+                                with_no_trimmed_paths(|| cx.tcx.def_path_str(trait_id));
+                            lint.span_suggestion(
+                                expr.span,
+                                "disambiguate the associated function",
+                                format!("<{} as {}>::{}", self_ty, trait_path, segment.ident),
+                                Applicability::MachineApplicable,
+                            );
+                        }
+                        _ => {
+                            lint.help(&format!(
+                                "call `{}` through the fully qualified path of its trait",
+                                segment.ident
+                            ));
+                        }
+                    }
+                    lint.emit();
+                });
+            }
+            _ => {}
+        }
+    }
+}
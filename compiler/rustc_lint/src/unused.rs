@@ -664,7 +664,9 @@ trait UnusedDelimLint {
     fn check_stmt(&mut self, cx: &EarlyContext<'_>, s: &ast::Stmt) {
         match s.kind {
             StmtKind::Local(ref local) if Self::LINT_EXPR_IN_PATTERN_MATCHING_CTX => {
-                if let Some(ref value) = local.init {
+                // The initializer of a `let...else` may need its parentheses, so only plain
+                // initializers are checked.
+                if let ast::LocalKind::Init(ref value) = local.kind {
                     self.check_unused_delims_expr(
                         cx,
                         &value,
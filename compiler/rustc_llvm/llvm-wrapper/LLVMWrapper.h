@@ -85,6 +85,9 @@ enum LLVMRustAttribute {
   ReturnsTwice = 25,
   ReadNone = 26,
   InaccessibleMemOnly = 27,
+  StackProtectReq = 28,
+  StackProtectStrong = 29,
+  StackProtect = 30,
 };
 
 typedef struct OpaqueRustString *RustStringRef;
@@ -970,6 +970,10 @@ impl<'a, 'tcx> CrateMetadataRef<'a> {
         self.root.tables.ty.get(self, id).unwrap().decode((self, tcx))
     }
 
+    fn get_const_param_default(&self, tcx: TyCtxt<'tcx>, id: DefIndex) -> ty::Const<'tcx> {
+        self.root.tables.const_defaults.get(self, id).unwrap().decode((self, tcx))
+    }
+
     fn get_stability(&self, id: DefIndex) -> Option<attr::Stability> {
         match self.is_proc_macro(id) {
             true => self.root.proc_macro_data.as_ref().unwrap().stability,
@@ -225,9 +225,26 @@ pub fn struct_lint_level<'s, 'd>(
         span: Option<MultiSpan>,
         decorate: Box<dyn for<'b> FnOnce(LintDiagnosticBuilder<'b>) + 'd>,
     ) {
+        // Check for future incompatibility lints and issue a stronger warning.
+        let lint_id = LintId::of(lint);
+        let future_incompatible = lint.future_incompatible;
+
+        // Lints that will become hard errors (rather than edition changes) are recorded
+        // for the future-incompatibility report even when they are allowed.
+        let has_future_breakage =
+            future_incompatible.map_or(false, |incompat| incompat.edition.is_none());
+
         let mut err = match (level, span) {
-            (Level::Allow, _) => {
-                return;
+            (Level::Allow, span) => {
+                if has_future_breakage && sess.opts.debugging_opts.emit_future_incompat_report {
+                    if let Some(span) = span {
+                        sess.struct_span_allow(span, "")
+                    } else {
+                        sess.struct_allow("")
+                    }
+                } else {
+                    return;
+                }
             }
             (Level::Warn, Some(span)) => sess.struct_span_warn(span, ""),
             (Level::Warn, None) => sess.struct_warn(""),
@@ -235,10 +252,6 @@ pub fn struct_lint_level<'s, 'd>(
             (Level::Deny | Level::Forbid, None) => sess.struct_err(""),
         };
 
-        // Check for future incompatibility lints and issue a stronger warning.
-        let lint_id = LintId::of(lint);
-        let future_incompatible = lint.future_incompatible;
-
         // If this code originates in a foreign macro, aka something that this crate
         // did not itself author, then it's likely that there's nothing this crate
         // can do about it. We probably want to skip the lint entirely.
@@ -260,68 +273,72 @@ pub fn struct_lint_level<'s, 'd>(
         }
 
         let name = lint.name_lower();
-        match src {
-            LintSource::Default => {
-                sess.diag_note_once(
-                    &mut err,
-                    DiagnosticMessageId::from(lint),
-                    &format!("`#[{}({})]` on by default", level.as_str(), name),
-                );
-            }
-            LintSource::CommandLine(lint_flag_val) => {
-                let flag = match level {
-                    Level::Warn => "-W",
-                    Level::Deny => "-D",
-                    Level::Forbid => "-F",
-                    Level::Allow => panic!(),
-                };
-                let hyphen_case_lint_name = name.replace("_", "-");
-                if lint_flag_val.as_str() == name {
-                    sess.diag_note_once(
-                        &mut err,
-                        DiagnosticMessageId::from(lint),
-                        &format!(
-                            "requested on the command line with `{} {}`",
-                            flag, hyphen_case_lint_name
-                        ),
-                    );
-                } else {
-                    let hyphen_case_flag_val = lint_flag_val.as_str().replace("_", "-");
+        // Allowed lints are only built for the future-incompatibility report and never shown, so
+        // they must not use up the notes that are only shown once.
+        if level != Level::Allow {
+            match src {
+                LintSource::Default => {
                     sess.diag_note_once(
                         &mut err,
                         DiagnosticMessageId::from(lint),
-                        &format!(
-                            "`{} {}` implied by `{} {}`",
-                            flag, hyphen_case_lint_name, flag, hyphen_case_flag_val
-                        ),
+                        &format!("`#[{}({})]` on by default", level.as_str(), name),
                     );
                 }
-            }
-            LintSource::Node(lint_attr_name, src, reason) => {
-                if let Some(rationale) = reason {
-                    err.note(&rationale.as_str());
+                LintSource::CommandLine(lint_flag_val) => {
+                    let flag = match level {
+                        Level::Warn => "-W",
+                        Level::Deny => "-D",
+                        Level::Forbid => "-F",
+                        Level::Allow => "-A",
+                    };
+                    let hyphen_case_lint_name = name.replace("_", "-");
+                    if lint_flag_val.as_str() == name {
+                        sess.diag_note_once(
+                            &mut err,
+                            DiagnosticMessageId::from(lint),
+                            &format!(
+                                "requested on the command line with `{} {}`",
+                                flag, hyphen_case_lint_name
+                            ),
+                        );
+                    } else {
+                        let hyphen_case_flag_val = lint_flag_val.as_str().replace("_", "-");
+                        sess.diag_note_once(
+                            &mut err,
+                            DiagnosticMessageId::from(lint),
+                            &format!(
+                                "`{} {}` implied by `{} {}`",
+                                flag, hyphen_case_lint_name, flag, hyphen_case_flag_val
+                            ),
+                        );
+                    }
                 }
-                sess.diag_span_note_once(
-                    &mut err,
-                    DiagnosticMessageId::from(lint),
-                    src,
-                    "the lint level is defined here",
-                );
-                if lint_attr_name.as_str() != name {
-                    let level_str = level.as_str();
-                    sess.diag_note_once(
+                LintSource::Node(lint_attr_name, src, reason) => {
+                    if let Some(rationale) = reason {
+                        err.note(&rationale.as_str());
+                    }
+                    sess.diag_span_note_once(
                         &mut err,
                         DiagnosticMessageId::from(lint),
-                        &format!(
-                            "`#[{}({})]` implied by `#[{}({})]`",
-                            level_str, name, level_str, lint_attr_name
-                        ),
+                        src,
+                        "the lint level is defined here",
                     );
+                    if lint_attr_name.as_str() != name {
+                        let level_str = level.as_str();
+                        sess.diag_note_once(
+                            &mut err,
+                            DiagnosticMessageId::from(lint),
+                            &format!(
+                                "`#[{}({})]` implied by `#[{}({})]`",
+                                level_str, name, level_str, lint_attr_name
+                            ),
+                        );
+                    }
                 }
             }
         }
 
-        err.code(DiagnosticId::Lint(name));
+        err.code(DiagnosticId::Lint { name, has_future_breakage });
 
         if let Some(future_incompatible) = future_incompatible {
             const STANDARD_MESSAGE: &str = "this was previously accepted by the compiler but is being phased out; \
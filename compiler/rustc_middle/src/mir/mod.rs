@@ -2294,11 +2294,12 @@ impl<'tcx> Debug for Rvalue<'tcx> {
                             };
                             let mut struct_fmt = fmt.debug_struct(&name);
 
-                            if let Some(upvars) = tcx.upvars_mentioned(def_id) {
-                                for (&var_id, place) in upvars.keys().zip(places) {
-                                    let var_name = tcx.hir().name(var_id);
-                                    struct_fmt.field(&var_name.as_str(), place);
-                                }
+                            for (captured_place, place) in tcx
+                                .typeck(def_id)
+                                .closure_min_captures_flattened(def_id.to_def_id())
+                                .zip(places)
+                            {
+                                struct_fmt.field(&captured_place.to_string(tcx), place);
                             }
 
                             struct_fmt.finish()
@@ -2313,11 +2314,12 @@ impl<'tcx> Debug for Rvalue<'tcx> {
                             let name = format!("[generator@{:?}]", tcx.hir().span(hir_id));
                             let mut struct_fmt = fmt.debug_struct(&name);
 
-                            if let Some(upvars) = tcx.upvars_mentioned(def_id) {
-                                for (&var_id, place) in upvars.keys().zip(places) {
-                                    let var_name = tcx.hir().name(var_id);
-                                    struct_fmt.field(&var_name.as_str(), place);
-                                }
+                            for (captured_place, place) in tcx
+                                .typeck(def_id)
+                                .closure_min_captures_flattened(def_id.to_def_id())
+                                .zip(places)
+                            {
+                                struct_fmt.field(&captured_place.to_string(tcx), place);
                             }
 
                             struct_fmt.finish()
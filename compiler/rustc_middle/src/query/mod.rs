@@ -118,6 +118,12 @@ rustc_queries! {
             cache_on_disk_if { key.is_local() }
         }
 
+        /// Given the `DefId` of a const parameter with a default, computes that default,
+        /// e.g. `3` for `N` in `struct Foo<const N: usize = 3>`.
+        query const_param_default(param: DefId) -> &'tcx ty::Const<'tcx> {
+            desc { |tcx| "computing const default for a given parameter `{}`", tcx.def_path_str(param) }
+        }
+
         query analysis(key: CrateNum) -> Result<(), ErrorReported> {
             eval_always
             desc { "running analysis passes on this crate" }
@@ -203,7 +203,11 @@ pub trait Printer<'tcx>: Sized {
                                     self.tcx().type_of(param.def_id).subst(self.tcx(), substs),
                                 )
                     }
-                    ty::GenericParamDefKind::Const => false, // FIXME(const_generics:defaults)
+                    ty::GenericParamDefKind::Const { has_default } => {
+                        has_default
+                            && substs[param.index as usize]
+                                == GenericArg::from(self.tcx().const_param_default(param.def_id))
+                    }
                 }
             })
             .count();
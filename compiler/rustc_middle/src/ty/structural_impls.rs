@@ -306,6 +306,7 @@ CloneTypeFoldableAndLiftImpls! {
     crate::traits::Reveal,
     crate::ty::adjustment::AutoBorrowMutability,
     crate::ty::AdtKind,
+    crate::ty::BorrowKind,
     // Including `BoundRegion` is a *bit* dubious, but direct
     // references to bound region appear in `ty::Error`, and aren't
     // really meant to be folded. In general, we can only fold a fully
@@ -322,6 +323,7 @@ CloneTypeFoldableAndLiftImpls! {
     crate::ty::adjustment::PointerCast,
     crate::ty::RegionVid,
     crate::ty::UniverseIndex,
+    crate::ty::UpvarId,
     crate::ty::Variance,
     ::rustc_span::Span,
 }
@@ -155,28 +155,20 @@ fn do_mir_borrowck<'a, 'tcx>(
         infcx.set_tainted_by_errors();
     }
     let upvars: Vec<_> = tables
-        .closure_captures
-        .get(&def.did.to_def_id())
-        .into_iter()
-        .flat_map(|v| v.values())
-        .map(|upvar_id| {
-            let var_hir_id = upvar_id.var_path.hir_id;
-            let capture = tables.upvar_capture(*upvar_id);
-            let by_ref = match capture {
+        .closure_min_captures_flattened(def.did.to_def_id())
+        .map(|captured_place| {
+            let var_hir_id = captured_place.get_root_variable();
+            let by_ref = match captured_place.info.capture_kind {
                 ty::UpvarCapture::ByValue(_) => false,
                 ty::UpvarCapture::ByRef(..) => true,
             };
-            let mut upvar = Upvar {
-                name: tcx.hir().name(var_hir_id),
-                var_hir_id,
-                by_ref,
-                mutability: Mutability::Not,
+            // Name a place that is captured in part after its path, e.g. `s.x`.
+            let name = if captured_place.place.projections.is_empty() {
+                tcx.hir().name(var_hir_id)
+            } else {
+                Symbol::intern(&captured_place.to_string(tcx))
             };
-            let bm = *tables.pat_binding_modes().get(var_hir_id).expect("missing binding mode");
-            if bm == ty::BindByValue(hir::Mutability::Mut) {
-                upvar.mutability = Mutability::Mut;
-            }
-            upvar
+            Upvar { name, var_hir_id, by_ref, mutability: captured_place.mutability }
         })
         .collect();
 
@@ -0,0 +1,334 @@
+//! Scalar replacement of aggregates.
+//!
+//! This pass splits locals of struct and tuple type into one local per field, so that later
+//! passes and codegen deal with scalars instead of copying whole aggregates around. For example
+//!
+//!     StorageLive(_1);
+//!     (_1.0: i32) = const 1_i32;
+//!     (_1.1: i32) = const 2_i32;
+//!     _2 = (_1.1: i32);
+//!     StorageDead(_1);
+//!
+//! becomes
+//!
+//!     StorageLive(_3);
+//!     StorageLive(_4);
+//!     _3 = const 1_i32;
+//!     _4 = const 2_i32;
+//!     _2 = _4;
+//!     StorageDead(_3);
+//!     StorageDead(_4);
+//!
+//! A local is only replaced if it is used exclusively
+//!
+//! * through field projections whose address is never taken,
+//! * as the destination of an `Rvalue::Use` of a place, which gets split into one assignment per
+//!   field,
+//! * in `StorageLive` and `StorageDead` statements.
+//!
+//! Aggregates are built field by field at this point, as `Deaggregator` has already run.
+//! Arguments, the return place and locals of types that implement `Drop` are never replaced.
+//! Fields that are never accessed on their own do not get a replacement local, and are left out
+//! when splitting an assignment of the whole local. The replaced locals themselves are left
+//! unused, for `SimplifyLocals` to clean up.
+//!
+//! Debuginfo can only describe a user variable with a single place, so a local that is a user
+//! variable is only replaced when no debuginfo is emitted.
+
+use crate::transform::MirPass;
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::visit::{
+    MutVisitor, MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor,
+};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_session::config::DebugInfo;
+
+pub struct ScalarReplacementOfAggregates;
+
+impl<'tcx> MirPass<'tcx> for ScalarReplacementOfAggregates {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if tcx.sess.opts.debugging_opts.mir_opt_level < 2 {
+            return;
+        }
+
+        trace!("Running ScalarReplacementOfAggregates on {:?}", body.source);
+
+        let keep_debuginfo = tcx.sess.opts.debuginfo != DebugInfo::None;
+        let escaping = escaping_locals(tcx, body, keep_debuginfo);
+        let replacements = compute_replacements(body, &escaping);
+        if replacements.is_empty() {
+            return;
+        }
+
+        replace_flattened_locals(tcx, body, replacements);
+    }
+}
+
+/// Returns whether locals of type `ty` can be split into their fields.
+fn is_splittable_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> bool {
+    match ty.kind() {
+        ty::Tuple(fields) => !fields.is_empty(),
+        ty::Adt(def, _) => {
+            def.is_struct()
+                && !def.repr.simd()
+                && !def.has_dtor(tcx)
+                && !def.non_enum_variant().fields.is_empty()
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether `context` takes the address of the place it is used for.
+fn takes_address(context: PlaceContext) -> bool {
+    context.is_borrow()
+        || matches!(
+            context,
+            PlaceContext::MutatingUse(MutatingUseContext::AddressOf)
+                | PlaceContext::NonMutatingUse(NonMutatingUseContext::AddressOf)
+        )
+}
+
+/// Computes the set of locals that cannot be replaced, either because of their type or because
+/// they are used in a way that requires the whole aggregate.
+fn escaping_locals<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    keep_debuginfo: bool,
+) -> BitSet<Local> {
+    let mut escaping = BitSet::new_empty(body.local_decls.len());
+    for (local, decl) in body.local_decls.iter_enumerated() {
+        if local.index() <= body.arg_count || !is_splittable_ty(tcx, decl.ty) {
+            escaping.insert(local);
+        }
+    }
+
+    let mut visitor = EscapeVisitor { escaping, keep_debuginfo };
+    visitor.visit_body(body);
+    visitor.escaping
+}
+
+struct EscapeVisitor {
+    escaping: BitSet<Local>,
+    keep_debuginfo: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for EscapeVisitor {
+    fn visit_local(&mut self, local: &Local, _: PlaceContext, _: Location) {
+        self.escaping.insert(*local);
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        // Fields can be replaced, as long as their address is not taken. We still need to visit
+        // the rest of the projection for the locals used as indices.
+        if let [PlaceElem::Field(..), ..] = place.projection[..] {
+            if !takes_address(context) {
+                self.visit_projection(place.local, place.projection, context, location);
+                return;
+            }
+        }
+
+        self.super_place(place, context, location);
+    }
+
+    fn visit_assign(&mut self, place: &Place<'tcx>, rvalue: &Rvalue<'tcx>, location: Location) {
+        // Copies and moves of whole aggregates are split into one assignment per field.
+        if place.as_local().is_some() {
+            if let Rvalue::Use(Operand::Copy(_) | Operand::Move(_)) = rvalue {
+                self.visit_rvalue(rvalue, location);
+                return;
+            }
+        }
+
+        self.super_assign(place, rvalue, location);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        match statement.kind {
+            // Storage statements are split into one statement per field.
+            StatementKind::StorageLive(_) | StatementKind::StorageDead(_) => {}
+            _ => self.super_statement(statement, location),
+        }
+    }
+
+    fn visit_var_debug_info(&mut self, var_debug_info: &VarDebugInfo<'tcx>) {
+        // Debuginfo for a whole local is removed when the local is replaced, which is only
+        // fine if we do not emit debuginfo in the first place.
+        if var_debug_info.place.projection.is_empty() && !self.keep_debuginfo {
+            return;
+        }
+
+        self.super_var_debug_info(var_debug_info);
+    }
+}
+
+/// The replacement locals for the fields of each local, if any.
+struct ReplacementMap<'tcx> {
+    fragments: IndexVec<Local, Option<IndexVec<Field, Option<(Ty<'tcx>, Local)>>>>,
+}
+
+impl<'tcx> ReplacementMap<'tcx> {
+    fn is_empty(&self) -> bool {
+        self.fragments.iter().all(Option::is_none)
+    }
+
+    fn is_replaced(&self, local: Local) -> bool {
+        self.fragments[local].is_some()
+    }
+
+    fn replace_field(&self, local: Local, field: Field) -> Option<Local> {
+        let fields = self.fragments[local].as_ref()?;
+        let (_, new_local) = (*fields.get(field)?)?;
+        Some(new_local)
+    }
+
+    /// Returns the fields of `local` that have a replacement, if `local` is replaced.
+    fn fields(&self, local: Local) -> Option<impl Iterator<Item = (Field, Ty<'tcx>, Local)> + '_> {
+        let fields = self.fragments[local].as_ref()?;
+        Some(fields.iter_enumerated().filter_map(|(field, fragment)| {
+            let (ty, new_local) = (*fragment)?;
+            Some((field, ty, new_local))
+        }))
+    }
+}
+
+/// Collects the fields used on their own in each local that can be replaced, and creates a new
+/// local for each of them.
+fn compute_replacements<'tcx>(
+    body: &mut Body<'tcx>,
+    escaping: &BitSet<Local>,
+) -> ReplacementMap<'tcx> {
+    let mut collector = FieldCollector {
+        escaping,
+        fields: IndexVec::from_elem(IndexVec::new(), &body.local_decls),
+    };
+    collector.visit_body(body);
+
+    let mut fragments = IndexVec::from_elem(None, &body.local_decls);
+    for (local, fields) in collector.fields.into_iter_enumerated() {
+        if fields.iter().all(Option::is_none) {
+            continue;
+        }
+
+        let span = body.local_decls[local].source_info.span;
+        let fields = fields
+            .into_iter()
+            .map(|ty| {
+                let ty = ty?;
+                Some((ty, body.local_decls.push(LocalDecl::new(ty, span))))
+            })
+            .collect();
+        debug!("replacing {:?} with {:?}", local, fields);
+        fragments[local] = Some(fields);
+    }
+
+    ReplacementMap { fragments }
+}
+
+struct FieldCollector<'a, 'tcx> {
+    escaping: &'a BitSet<Local>,
+    fields: IndexVec<Local, IndexVec<Field, Option<Ty<'tcx>>>>,
+}
+
+impl<'tcx> Visitor<'tcx> for FieldCollector<'_, 'tcx> {
+    fn visit_place(&mut self, place: &Place<'tcx>, _: PlaceContext, _: Location) {
+        if self.escaping.contains(place.local) {
+            return;
+        }
+
+        if let [PlaceElem::Field(field, ty), ..] = place.projection[..] {
+            let fields = &mut self.fields[place.local];
+            fields.ensure_contains_elem(field, || None);
+            fields[field] = Some(ty);
+        }
+    }
+}
+
+/// Replaces the uses of fields of the locals in `replacements` by the new locals, and splits
+/// the statements that use those locals as a whole.
+fn replace_flattened_locals<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    replacements: ReplacementMap<'tcx>,
+) {
+    for bb in body.basic_blocks_mut() {
+        bb.expand_statements(|stmt| {
+            expand_statement(tcx, &replacements, stmt).map(Vec::into_iter)
+        });
+    }
+
+    body.var_debug_info.retain(|var_debug_info| {
+        !(var_debug_info.place.projection.is_empty()
+            && replacements.is_replaced(var_debug_info.place.local))
+    });
+
+    let mut visitor = ReplacementVisitor { tcx, replacements };
+    visitor.visit_body(body);
+}
+
+/// Splits a statement that uses a replaced local as a whole into one statement per field. The
+/// statement is removed if none of the fields of the local are used on their own.
+fn expand_statement<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    replacements: &ReplacementMap<'tcx>,
+    stmt: &mut Statement<'tcx>,
+) -> Option<Vec<Statement<'tcx>>> {
+    let source_info = stmt.source_info;
+    let statement = |kind| Statement { source_info, kind };
+
+    let new_statements = match stmt.kind {
+        StatementKind::StorageLive(local) => replacements
+            .fields(local)?
+            .map(|(_, _, new_local)| statement(StatementKind::StorageLive(new_local)))
+            .collect(),
+        StatementKind::StorageDead(local) => replacements
+            .fields(local)?
+            .map(|(_, _, new_local)| statement(StatementKind::StorageDead(new_local)))
+            .collect(),
+        StatementKind::Assign(box (place, Rvalue::Use(ref operand))) => {
+            let fields = replacements.fields(place.as_local()?)?;
+            let (src, is_move) = match *operand {
+                Operand::Copy(src) => (src, false),
+                Operand::Move(src) => (src, true),
+                Operand::Constant(_) => bug!("splitting the assignment of a constant"),
+            };
+            fields
+                .map(|(field, ty, new_local)| {
+                    let src = tcx.mk_place_field(src, field, ty);
+                    let operand = if is_move { Operand::Move(src) } else { Operand::Copy(src) };
+                    statement(StatementKind::Assign(box (
+                        Place::from(new_local),
+                        Rvalue::Use(operand),
+                    )))
+                })
+                .collect()
+        }
+        _ => return None,
+    };
+
+    Some(new_statements)
+}
+
+struct ReplacementVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    replacements: ReplacementMap<'tcx>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for ReplacementVisitor<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_place(&mut self, place: &mut Place<'tcx>, context: PlaceContext, location: Location) {
+        let projection = place.projection;
+        if let [PlaceElem::Field(field, _), ref rest @ ..] = projection[..] {
+            if let Some(new_local) = self.replacements.replace_field(place.local, field) {
+                *place = Place { local: new_local, projection: self.tcx.intern_place_elems(rest) };
+                return;
+            }
+        }
+
+        self.super_place(place, context, location);
+    }
+}
@@ -4,14 +4,41 @@ use crate::build::expr::category::Category;
 use crate::build::ForGuard::{OutsideGuard, RefWithinGuard};
 use crate::build::{BlockAnd, BlockAndExtension, Builder};
 use crate::thir::*;
+use rustc_hir::def_id::DefId;
+use rustc_hir::HirId;
+use rustc_middle::hir::place::ProjectionKind as HirProjectionKind;
 use rustc_middle::middle::region;
 use rustc_middle::mir::AssertKind::BoundsCheck;
 use rustc_middle::mir::*;
 use rustc_middle::ty::{self, CanonicalUserTypeAnnotation, Ty, TyCtxt, Variance};
 use rustc_span::Span;
+use rustc_target::abi::VariantIdx;
 
 use rustc_index::vec::Idx;
 
+/// The "outermost" place that holds this value.
+#[derive(Copy, Clone)]
+crate enum PlaceBase {
+    /// Denotes the start of a `Place`.
+    Local(Local),
+
+    /// A place within a closure that starts off the upvar `var_hir_id`.
+    ///
+    /// When a closure captures disjoint fields, which capture of the closure holds the place
+    /// (e.g. `t.0` or `t.0.1` for the place `t.0.1.2`) is only known once all of the place's
+    /// projections have been pushed. The place is resolved to a projection of the closure
+    /// environment `_1` at that point, see `into_place`.
+    Upvar {
+        /// `HirId` of the root variable.
+        var_hir_id: HirId,
+        /// `DefId` of the closure.
+        closure_def_id: DefId,
+        /// How the closure takes its environment: by reference for `Fn` and `FnMut`, or by
+        /// value for `FnOnce` and generators.
+        closure_kind: ty::ClosureKind,
+    },
+}
+
 /// `PlaceBuilder` is used to create places during MIR construction. It allows you to "build up" a
 /// place by pushing more and more projections onto the end, and then convert the final set into a
 /// place using the `into_place` method.
@@ -19,14 +46,181 @@ use rustc_index::vec::Idx;
 /// This is used internally when building a place for an expression like `a.b.c`. The fields `b`
 /// and `c` can be progressively pushed onto the place builder that is created when converting `a`.
 #[derive(Clone)]
-struct PlaceBuilder<'tcx> {
-    local: Local,
+crate struct PlaceBuilder<'tcx> {
+    base: PlaceBase,
     projection: Vec<PlaceElem<'tcx>>,
 }
 
+/// Converts MIR projections to the HIR projections that a closure can capture, stopping at the
+/// first projection that is never part of a captured place: a downcast (enums with several
+/// variants are captured as a whole) or an array access.
+fn convert_to_hir_projections_and_truncate_for_capture<'tcx>(
+    mir_projections: &[PlaceElem<'tcx>],
+) -> Vec<HirProjectionKind> {
+    let mut hir_projections = Vec::new();
+
+    for mir_projection in mir_projections {
+        let hir_projection = match mir_projection {
+            ProjectionElem::Deref => HirProjectionKind::Deref,
+            ProjectionElem::Field(field, _) => {
+                HirProjectionKind::Field(field.index() as u32, VariantIdx::new(0))
+            }
+            ProjectionElem::Downcast(..)
+            | ProjectionElem::Index(..)
+            | ProjectionElem::ConstantIndex { .. }
+            | ProjectionElem::Subslice { .. } => break,
+        };
+
+        hir_projections.push(hir_projection);
+    }
+
+    hir_projections
+}
+
+/// Returns whether the projections `proj_possible_ancestor` are a prefix of `proj_capture`, i.e.
+/// whether a place with the former projections is an ancestor of or the same as a place with
+/// the latter projections, provided that both places start off the same root variable.
+fn is_ancestor_or_same_capture(
+    proj_possible_ancestor: &[HirProjectionKind],
+    proj_capture: &[HirProjectionKind],
+) -> bool {
+    proj_possible_ancestor.len() <= proj_capture.len()
+        && proj_possible_ancestor.iter().zip(proj_capture).all(|(a, b)| a == b)
+}
+
+/// Returns the index of the field of the closure environment that holds the `root_var_idx`-th
+/// captured place of the root variable `var_hir_id`.
+fn compute_capture_idx<'tcx>(
+    closure_min_captures: &ty::RootVariableMinCaptureList<'tcx>,
+    var_hir_id: HirId,
+    root_var_idx: usize,
+) -> usize {
+    let preceding_captures: usize = closure_min_captures
+        .iter()
+        .take_while(|(var_id, _)| **var_id != var_hir_id)
+        .map(|(_, capture_list)| capture_list.len())
+        .sum();
+    preceding_captures + root_var_idx
+}
+
+/// Returns the place captured by the closure `closure_def_id` that is an ancestor of (or the
+/// same as) the place that starts off `var_hir_id` with the projections `projections`, along
+/// with the index of its field in the closure environment.
+///
+/// Since captured places are disjoint, there is at most one such place.
+fn find_capture_matching_projections<'a, 'tcx>(
+    typeck_results: &'a ty::TypeckResults<'tcx>,
+    var_hir_id: HirId,
+    closure_def_id: DefId,
+    projections: &[PlaceElem<'tcx>],
+) -> Option<(usize, &'a ty::CapturedPlace<'tcx>)> {
+    let closure_min_captures = typeck_results.closure_min_captures.get(&closure_def_id)?;
+    let root_variable_min_captures = closure_min_captures.get(&var_hir_id)?;
+
+    let hir_projections = convert_to_hir_projections_and_truncate_for_capture(projections);
+
+    let (idx, capture) = root_variable_min_captures.iter().enumerate().find(|(_, capture)| {
+        let possible_ancestor_proj_kinds: Vec<_> =
+            capture.place.projections.iter().map(|proj| proj.kind).collect();
+        is_ancestor_or_same_capture(&possible_ancestor_proj_kinds, &hir_projections)
+    })?;
+
+    Some((compute_capture_idx(closure_min_captures, var_hir_id, idx), capture))
+}
+
+/// Resolves the upvar that `from_builder` starts off, if any, to the field of the closure
+/// environment that captures it, so that the returned builder starts off a `PlaceBase::Local`.
+fn to_upvars_resolved_place_builder<'tcx>(
+    from_builder: PlaceBuilder<'tcx>,
+    tcx: TyCtxt<'tcx>,
+    typeck_results: &ty::TypeckResults<'tcx>,
+) -> PlaceBuilder<'tcx> {
+    match from_builder.base {
+        PlaceBase::Local(_) => from_builder,
+        PlaceBase::Upvar { var_hir_id, closure_def_id, closure_kind } => {
+            // Captures are fields of the closure environment, which is the first argument.
+            let mut upvar_resolved_place_builder = PlaceBuilder::from(Local::new(1));
+            match closure_kind {
+                ty::ClosureKind::Fn | ty::ClosureKind::FnMut => {
+                    upvar_resolved_place_builder = upvar_resolved_place_builder.deref();
+                }
+                ty::ClosureKind::FnOnce => {}
+            }
+
+            let (capture_index, capture) = find_capture_matching_projections(
+                typeck_results,
+                var_hir_id,
+                closure_def_id,
+                &from_builder.projection,
+            )
+            .unwrap_or_else(|| {
+                bug!(
+                    "no capture of {:?} in closure {:?} covers the projections {:?}",
+                    var_hir_id,
+                    closure_def_id,
+                    from_builder.projection
+                )
+            });
+
+            let closure_ty = typeck_results
+                .node_type(tcx.hir().local_def_id_to_hir_id(closure_def_id.expect_local()));
+
+            let substs = match closure_ty.kind() {
+                ty::Closure(_, substs) => ty::UpvarSubsts::Closure(substs),
+                ty::Generator(_, substs, _) => ty::UpvarSubsts::Generator(substs),
+                _ => bug!("Lowering capture for non-closure type {:?}", closure_ty),
+            };
+
+            // Upvar inference has run by now, and the capture exists, so this can't fail.
+            let var_ty = substs.upvar_tys().nth(capture_index).unwrap();
+
+            upvar_resolved_place_builder =
+                upvar_resolved_place_builder.field(Field::new(capture_index), var_ty);
+
+            // A place that is captured by reference is behind the reference in its field.
+            upvar_resolved_place_builder = match capture.info.capture_kind {
+                ty::UpvarCapture::ByRef(_) => upvar_resolved_place_builder.deref(),
+                ty::UpvarCapture::ByValue(_) => upvar_resolved_place_builder,
+            };
+
+            let next_projection = capture.place.projections.len();
+            let mut curr_projections = from_builder.projection;
+
+            // The projections of the captured place are accounted for by the field access;
+            // apply the remaining ones to it.
+            upvar_resolved_place_builder
+                .projection
+                .extend(curr_projections.drain(next_projection..));
+
+            upvar_resolved_place_builder
+        }
+    }
+}
+
 impl<'tcx> PlaceBuilder<'tcx> {
-    fn into_place(self, tcx: TyCtxt<'tcx>) -> Place<'tcx> {
-        Place { local: self.local, projection: tcx.intern_place_elems(&self.projection) }
+    crate fn into_place(
+        self,
+        tcx: TyCtxt<'tcx>,
+        typeck_results: &ty::TypeckResults<'tcx>,
+    ) -> Place<'tcx> {
+        if let PlaceBase::Local(local) = self.base {
+            Place { local, projection: tcx.intern_place_elems(&self.projection) }
+        } else {
+            self.expect_upvars_resolved(tcx, typeck_results).into_place(tcx, typeck_results)
+        }
+    }
+
+    /// Resolves the upvar this builder starts off, if any; see `PlaceBase::Upvar`.
+    fn expect_upvars_resolved(
+        self,
+        tcx: TyCtxt<'tcx>,
+        typeck_results: &ty::TypeckResults<'tcx>,
+    ) -> PlaceBuilder<'tcx> {
+        to_upvars_resolved_place_builder(self, tcx, typeck_results)
+    }
+
+    crate fn base(&self) -> PlaceBase {
+        self.base
     }
 
     fn field(self, f: Field, ty: Ty<'tcx>) -> Self {
@@ -49,7 +243,13 @@ impl<'tcx> PlaceBuilder<'tcx> {
 
 impl<'tcx> From<Local> for PlaceBuilder<'tcx> {
     fn from(local: Local) -> Self {
-        Self { local, projection: Vec::new() }
+        Self { base: PlaceBase::Local(local), projection: Vec::new() }
+    }
+}
+
+impl<'tcx> From<PlaceBase> for PlaceBuilder<'tcx> {
+    fn from(base: PlaceBase) -> Self {
+        Self { base, projection: Vec::new() }
     }
 }
 
@@ -71,12 +271,16 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         M: Mirror<'tcx, Output = Expr<'tcx>>,
     {
         let place_builder = unpack!(block = self.as_place_builder(block, expr));
-        block.and(place_builder.into_place(self.hir.tcx()))
+        block.and(place_builder.into_place(self.hir.tcx(), self.hir.typeck_results()))
     }
 
     /// This is used when constructing a compound `Place`, so that we can avoid creating
     /// intermediate `Place` values until we know the full set of projections.
-    fn as_place_builder<M>(&mut self, block: BasicBlock, expr: M) -> BlockAnd<PlaceBuilder<'tcx>>
+    crate fn as_place_builder<M>(
+        &mut self,
+        block: BasicBlock,
+        expr: M,
+    ) -> BlockAnd<PlaceBuilder<'tcx>>
     where
         M: Mirror<'tcx, Output = Expr<'tcx>>,
     {
@@ -98,7 +302,7 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         M: Mirror<'tcx, Output = Expr<'tcx>>,
     {
         let place_builder = unpack!(block = self.as_read_only_place_builder(block, expr));
-        block.and(place_builder.into_place(self.hir.tcx()))
+        block.and(place_builder.into_place(self.hir.tcx(), self.hir.typeck_results()))
     }
 
     /// This is used when constructing a compound `Place`, so that we can avoid creating
@@ -160,7 +364,20 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                 expr_span,
                 source_info,
             ),
-            ExprKind::SelfRef => block.and(PlaceBuilder::from(Local::new(1))),
+            ExprKind::UpvarRef { closure_def_id, var_hir_id } => {
+                // The closure environment is taken by reference if the closure implements
+                // `Fn` or `FnMut`, and by value otherwise.
+                let closure_kind = match this.local_decls[Local::new(1)].ty.kind() {
+                    ty::Ref(_, _, Mutability::Not) => ty::ClosureKind::Fn,
+                    ty::Ref(_, _, Mutability::Mut) => ty::ClosureKind::FnMut,
+                    _ => ty::ClosureKind::FnOnce,
+                };
+                block.and(PlaceBuilder::from(PlaceBase::Upvar {
+                    var_hir_id,
+                    closure_def_id,
+                    closure_kind,
+                }))
+            }
             ExprKind::VarRef { id } => {
                 let place_builder = if this.is_bound_var_in_guard(id) {
                     let index = this.var_local_id(id, RefWithinGuard);
@@ -185,7 +402,8 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                             inferred_ty: expr.ty,
                         });
 
-                    let place = place_builder.clone().into_place(this.hir.tcx());
+                    let place =
+                        place_builder.clone().into_place(this.hir.tcx(), this.hir.typeck_results());
                     this.cfg.push(
                         block,
                         Statement {
@@ -299,6 +517,10 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
 
         let base_place =
             unpack!(block = self.expr_as_place(block, lhs, mutability, Some(fake_borrow_temps),));
+        // Index projections are never part of a captured place, so the base can be resolved
+        // here, which the bounds check and the fake borrows below rely on.
+        let base_place =
+            base_place.expect_upvars_resolved(self.hir.tcx(), self.hir.typeck_results());
 
         // Making this a *fresh* temporary means we do not have to worry about
         // the index changing later: Nothing will ever change this temporary.
@@ -307,7 +529,7 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
 
         block = self.bounds_check(
             block,
-            base_place.clone().into_place(self.hir.tcx()),
+            base_place.clone().into_place(self.hir.tcx(), self.hir.typeck_results()),
             idx,
             expr_span,
             source_info,
@@ -365,8 +587,12 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         source_info: SourceInfo,
     ) {
         let tcx = self.hir.tcx();
-        let place_ty =
-            Place::ty_from(base_place.local, &base_place.projection, &self.local_decls, tcx);
+        let local = match base_place.base {
+            PlaceBase::Local(local) => local,
+            PlaceBase::Upvar { .. } => bug!("Expected PlaceBase::Local found Upvar"),
+        };
+
+        let place_ty = Place::ty_from(local, &base_place.projection, &self.local_decls, tcx);
         if let ty::Slice(_) = place_ty.ty.kind() {
             // We need to create fake borrows to ensure that the bounds
             // check that we just did stays valid. Since we can't assign to
@@ -376,7 +602,7 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                 match elem {
                     ProjectionElem::Deref => {
                         let fake_borrow_deref_ty = Place::ty_from(
-                            base_place.local,
+                            local,
                             &base_place.projection[..idx],
                             &self.local_decls,
                             tcx,
@@ -394,14 +620,14 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                             Rvalue::Ref(
                                 tcx.lifetimes.re_erased,
                                 BorrowKind::Shallow,
-                                Place { local: base_place.local, projection },
+                                Place { local, projection },
                             ),
                         );
                         fake_borrow_temps.push(fake_borrow_temp);
                     }
                     ProjectionElem::Index(_) => {
                         let index_ty = Place::ty_from(
-                            base_place.local,
+                            local,
                             &base_place.projection[..idx],
                             &self.local_decls,
                             tcx,
@@ -11,7 +11,8 @@ use rustc_ast::ptr::P;
 use rustc_ast::token::{self, TokenKind};
 use rustc_ast::util::classify;
 use rustc_ast::{AttrStyle, AttrVec, Attribute, MacCall, MacCallStmt, MacStmtStyle};
-use rustc_ast::{Block, BlockCheckMode, Expr, ExprKind, Local, Stmt, StmtKind, DUMMY_NODE_ID};
+use rustc_ast::{Block, BlockCheckMode, Expr, ExprKind, Local, LocalKind, Stmt, StmtKind};
+use rustc_ast::DUMMY_NODE_ID;
 use rustc_errors::{Applicability, PResult};
 use rustc_span::source_map::{BytePos, Span};
 use rustc_span::symbol::{kw, sym};
@@ -218,8 +219,67 @@ impl<'a> Parser<'a> {
                 return Err(err);
             }
         };
+        let kind = match init {
+            None => LocalKind::Decl,
+            Some(init) => {
+                if self.eat_keyword(kw::Else) {
+                    let els = self.parse_block()?;
+                    self.check_let_else_init_bool_expr(&init);
+                    self.check_let_else_init_trailing_brace(&init);
+                    self.sess.gated_spans.gate(sym::let_else, lo.to(self.prev_token.span));
+                    LocalKind::InitElse(init, els)
+                } else {
+                    LocalKind::Init(init)
+                }
+            }
+        };
         let hi = if self.token == token::Semi { self.token.span } else { self.prev_token.span };
-        Ok(P(ast::Local { ty, pat, init, id: DUMMY_NODE_ID, span: lo.to(hi), attrs }))
+        Ok(P(ast::Local { ty, pat, kind, id: DUMMY_NODE_ID, span: lo.to(hi), attrs }))
+    }
+
+    /// `let x = a && b else { .. }` reads as if the `else` belonged to `b`, so lazy boolean
+    /// initializers of a `let...else` must be parenthesized.
+    fn check_let_else_init_bool_expr(&self, init: &ast::Expr) {
+        if let ExprKind::Binary(op, ..) = init.kind {
+            if op.node.lazy() {
+                self.struct_span_err(
+                    init.span,
+                    &format!(
+                        "a `{}` expression cannot be directly assigned in `let...else`",
+                        op.node.to_string()
+                    ),
+                )
+                .multipart_suggestion(
+                    "wrap the expression in parentheses",
+                    vec![
+                        (init.span.shrink_to_lo(), "(".to_string()),
+                        (init.span.shrink_to_hi(), ")".to_string()),
+                    ],
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+            }
+        }
+    }
+
+    /// `let x = if a { b } else { c } else { .. }` is hard to read, so initializers of a
+    /// `let...else` that end with a `}` must be parenthesized.
+    fn check_let_else_init_trailing_brace(&self, init: &ast::Expr) {
+        if !classify::expr_requires_semi_to_be_stmt(init) {
+            self.struct_span_err(
+                init.span.with_lo(init.span.hi() - BytePos(1)),
+                "right curly brace `}` before `else` in a `let...else` statement not allowed",
+            )
+            .multipart_suggestion(
+                "try wrapping the expression in parentheses",
+                vec![
+                    (init.span.shrink_to_lo(), "(".to_string()),
+                    (init.span.shrink_to_hi(), ")".to_string()),
+                ],
+                Applicability::MachineApplicable,
+            )
+            .emit();
+        }
     }
 
     /// Parses the RHS of a local variable declaration (e.g., '= 14;').
@@ -420,13 +480,13 @@ impl<'a> Parser<'a> {
             StmtKind::Local(ref mut local) => {
                 if let Err(e) = self.expect_semi() {
                     // We might be at the `,` in `let x = foo<bar, baz>;`. Try to recover.
-                    match &mut local.init {
-                        Some(ref mut expr) => {
+                    match &mut local.kind {
+                        LocalKind::Init(expr) | LocalKind::InitElse(expr, _) => {
                             self.check_mistyped_turbofish_with_multiple_type_params(e, expr)?;
                             // We found `foo<bar, baz>`, have we fully recovered?
                             self.expect_semi()?;
                         }
-                        None => return Err(e),
+                        LocalKind::Decl => return Err(e),
                     }
                 }
                 eat_semi = false;
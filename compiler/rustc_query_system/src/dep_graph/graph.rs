@@ -862,6 +862,17 @@ pub struct WorkProduct {
     pub cgu_name: String,
     /// Saved file associated with this CGU.
     pub saved_file: Option<String>,
+    /// Saved split DWARF object (`.dwo` file) associated with this CGU, if
+    /// debuginfo is split out of the object file.
+    pub saved_dwarf_object: Option<String>,
+}
+
+impl WorkProduct {
+    /// Iterates over the names of all files saved in the incremental directory
+    /// for this work product.
+    pub fn saved_files(&self) -> impl Iterator<Item = &String> {
+        self.saved_file.iter().chain(self.saved_dwarf_object.iter())
+    }
 }
 
 #[derive(Clone)]
@@ -458,7 +458,8 @@ impl<'a> Resolver<'a> {
             ResolutionError::ParamInAnonConstInTyDefault(name) => {
                 let mut err = self.session.struct_span_err(
                     span,
-                    "constant values inside of type parameter defaults must not depend on generic parameters",
+                    "constant values inside of generic parameter defaults must not depend on \
+                     generic parameters",
                 );
                 err.span_label(
                     span,
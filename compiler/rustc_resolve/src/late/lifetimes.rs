@@ -873,12 +873,15 @@ impl<'a, 'tcx> Visitor<'tcx> for LifetimeContext<'a, 'tcx> {
                         self.visit_ty(&ty);
                     }
                 }
-                GenericParamKind::Const { ref ty, .. } => {
+                GenericParamKind::Const { ref ty, ref default } => {
                     let was_in_const_generic = self.is_in_const_generic;
                     self.is_in_const_generic = true;
                     walk_list!(self, visit_param_bound, param.bounds);
                     self.visit_ty(&ty);
                     self.is_in_const_generic = was_in_const_generic;
+                    if let Some(ref default) = default {
+                        self.visit_anon_const(default);
+                    }
                 }
             }
         }
@@ -1974,7 +1977,8 @@ impl<'a, 'tcx> LifetimeContext<'a, 'tcx> {
                             GenericParamDefKind::Type { object_lifetime_default, .. } => {
                                 Some(object_lifetime_default)
                             }
-                            GenericParamDefKind::Lifetime | GenericParamDefKind::Const => None,
+                            GenericParamDefKind::Lifetime
+                            | GenericParamDefKind::Const { .. } => None,
                         })
                         .collect()
                 })
@@ -8,14 +8,14 @@ use crate::search_paths::SearchPath;
 use crate::utils::NativeLibKind;
 use crate::{early_error, early_warn, Session};
 
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::impl_stable_hash_via_hash;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 
 use rustc_target::abi::{Align, TargetDataLayout};
 use rustc_target::spec::{Target, TargetTriple};
 
-use crate::parse::CrateConfig;
+use crate::parse::{CrateCheckConfig, CrateConfig};
 use rustc_feature::UnstableFeatures;
 use rustc_span::edition::{Edition, DEFAULT_EDITION, EDITION_NAME_LIST};
 use rustc_span::source_map::{FileName, FilePathMapping};
@@ -30,6 +30,7 @@ use std::collections::btree_map::{
 };
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
+use std::hash::Hash;
 use std::iter::{self, FromIterator};
 use std::path::{Path, PathBuf};
 use std::str::{self, FromStr};
@@ -321,6 +322,9 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A SARIF log describing all of the diagnostics of the compilation, consumed by code
+    /// scanning tools.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -538,6 +542,7 @@ impl_stable_hash_via_hash!(OutputFilenames);
 
 pub const RLINK_EXT: &str = "rlink";
 pub const RUST_CGU_EXT: &str = "rcgu";
+pub const DWARF_OBJECT_EXT: &str = "dwo";
 
 impl OutputFilenames {
     pub fn new(
@@ -593,6 +598,12 @@ impl OutputFilenames {
         self.with_extension(&extension)
     }
 
+    /// Gets the path where the split DWARF object (`.dwo` file) of the given
+    /// codegen unit should be placed on disk.
+    pub fn temp_path_dwo(&self, codegen_unit_name: Option<&str>) -> PathBuf {
+        self.temp_path_ext(DWARF_OBJECT_EXT, codegen_unit_name)
+    }
+
     pub fn with_extension(&self, extension: &str) -> PathBuf {
         let mut path = self.out_directory.join(&self.filestem);
         path.set_extension(extension);
@@ -695,6 +706,7 @@ impl DebuggingOptions {
             report_delayed_bugs: self.report_delayed_bugs,
             macro_backtrace: self.macro_backtrace,
             deduplicate_diagnostics: self.deduplicate_diagnostics,
+            emit_future_breakage_report: self.emit_future_incompat_report,
         }
     }
 }
@@ -831,6 +843,153 @@ pub fn build_configuration(sess: &Session, mut user_cfg: CrateConfig) -> CrateCo
     user_cfg
 }
 
+/// The parsed `--check-cfg` options.
+pub struct CheckCfg<T = String> {
+    /// The set of all `names()`, if `None` no name checking is performed.
+    pub names_valid: Option<FxHashSet<T>>,
+    /// The set of all `values()`, keyed by the name whose values are checked.
+    pub values_valid: FxHashMap<T, FxHashSet<T>>,
+    /// Whether the values of the well-known names are checked, as requested by `values()`.
+    pub well_known_values: bool,
+}
+
+impl<T> Default for CheckCfg<T> {
+    fn default() -> Self {
+        CheckCfg {
+            names_valid: Default::default(),
+            values_valid: Default::default(),
+            well_known_values: false,
+        }
+    }
+}
+
+impl<T> CheckCfg<T> {
+    fn map_data<O: Eq + Hash>(&self, f: impl Fn(&T) -> O) -> CheckCfg<O> {
+        CheckCfg {
+            names_valid: self
+                .names_valid
+                .as_ref()
+                .map(|names_valid| names_valid.iter().map(|a| f(a)).collect()),
+            values_valid: self
+                .values_valid
+                .iter()
+                .map(|(a, b)| (f(a), b.iter().map(|b| f(b)).collect()))
+                .collect(),
+            well_known_values: self.well_known_values,
+        }
+    }
+}
+
+/// Converts the crate `--check-cfg` options from `String` to `Symbol`.
+/// `rustc_interface::interface::Config` accepts this in the compiler configuration,
+/// but the symbol interner is not yet set up then, so we must convert it later.
+pub fn to_crate_check_config(cfg: CheckCfg) -> CrateCheckConfig {
+    cfg.map_data(|s| Symbol::intern(s))
+}
+
+impl CrateCheckConfig {
+    /// Fills a `CrateCheckConfig` with the names set by `default_configuration` and the
+    /// drivers, if names are checked at all.
+    fn fill_well_known_names(&mut self) {
+        // NOTE: This should be kept in sync with `default_configuration` and
+        // `fill_well_known_values`.
+        const WELL_KNOWN_NAMES: &[Symbol] = &[
+            // rustc
+            sym::unix,
+            sym::windows,
+            sym::target_os,
+            sym::target_family,
+            sym::target_arch,
+            sym::target_endian,
+            sym::target_pointer_width,
+            sym::target_env,
+            sym::target_vendor,
+            sym::target_thread_local,
+            sym::target_has_atomic_load_store,
+            sym::target_has_atomic,
+            sym::target_has_atomic_equal_alignment,
+            sym::target_feature,
+            sym::sanitize,
+            sym::debug_assertions,
+            sym::proc_macro,
+            sym::test,
+            sym::feature,
+            // rustdoc
+            sym::doc,
+            sym::doctest,
+            // miri
+            sym::miri,
+        ];
+
+        if let Some(names_valid) = &mut self.names_valid {
+            names_valid.extend(WELL_KNOWN_NAMES);
+        }
+    }
+
+    /// Fills a `CrateCheckConfig` with the values every built-in target may give to the
+    /// well-known names, if `values()` was requested.
+    fn fill_well_known_values(&mut self) {
+        if !self.well_known_values {
+            return;
+        }
+
+        // NOTE: This should be kept in sync with `default_configuration`. The values of
+        // `feature` and `target_feature` are not known in advance, so they are not checked.
+
+        // Names that never have a value.
+        for &name in &[
+            sym::unix,
+            sym::windows,
+            sym::target_thread_local,
+            sym::debug_assertions,
+            sym::proc_macro,
+            sym::test,
+            sym::doc,
+            sym::doctest,
+            sym::miri,
+        ] {
+            self.values_valid.entry(name).or_default();
+        }
+
+        // Values that do not depend on the target.
+        let atomic_values: Vec<Symbol> =
+            ["8", "16", "32", "64", "128", "ptr"].iter().map(|s| Symbol::intern(s)).collect();
+        for &name in &[
+            sym::target_has_atomic_load_store,
+            sym::target_has_atomic,
+            sym::target_has_atomic_equal_alignment,
+        ] {
+            self.values_valid.entry(name).or_default().extend(atomic_values.iter().copied());
+        }
+        self.values_valid.entry(sym::sanitize).or_default().extend(
+            SanitizerSet::all().into_iter().map(|sanitizer| Symbol::intern(&sanitizer.to_string())),
+        );
+
+        // Values that come from the built-in targets.
+        for target in rustc_target::spec::TARGETS {
+            let target = Target::search(&TargetTriple::from_triple(target))
+                .expect("built-in target could not be loaded");
+            let mut insert = |name, value: &str| {
+                self.values_valid.entry(name).or_default().insert(Symbol::intern(value));
+            };
+            insert(sym::target_os, &target.target_os);
+            if let Some(family) = &target.options.target_family {
+                insert(sym::target_family, family);
+            }
+            insert(sym::target_arch, &target.arch);
+            insert(sym::target_endian, &target.target_endian);
+            insert(sym::target_pointer_width, &target.target_pointer_width);
+            insert(sym::target_env, &target.target_env);
+            insert(sym::target_vendor, &target.target_vendor);
+        }
+    }
+
+    pub fn fill_well_known(&mut self) {
+        self.fill_well_known_names();
+        self.fill_well_known_values();
+    }
+}
+
 pub fn build_target_config(opts: &Options, target_override: Option<Target>) -> Config {
     let target_result = target_override.map_or_else(|| Target::search(&opts.target_triple), Ok);
     let target = target_result.unwrap_or_else(|e| {
@@ -967,6 +1126,7 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
     vec![
         opt::flag_s("h", "help", "Display this message"),
         opt::multi_s("", "cfg", "Configure the compilation environment", "SPEC"),
+        opt::multi("", "check-cfg", "Provide list of valid cfg options for checking", "SPEC"),
         opt::multi_s(
             "L",
             "",
@@ -1061,7 +1221,7 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "",
             "error-format",
             "How errors and other messages are produced",
-            "human|json|short",
+            "human|json|short|sarif",
         ),
         opt::multi_s("", "json", "Configure the JSON output of the compiler", "CONFIG"),
         opt::opt_s(
@@ -1207,12 +1367,13 @@ pub fn parse_error_format(
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
+            Some("sarif") => ErrorOutputType::Sarif,
 
             Some(arg) => early_error(
                 ErrorOutputType::HumanReadable(HumanReadableErrorType::Default(color)),
                 &format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{}`)",
+                    "argument for `--error-format` must be `human`, `json`, `short` or \
+                     `sarif` (instead was `{}`)",
                     arg
                 ),
             ),
@@ -1289,6 +1450,12 @@ fn check_debug_option_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_error(
+                ErrorOutputType::HumanReadable(json_rendered),
+                "`--error-format=sarif` is unstable",
+            );
+        }
     }
 }
 
@@ -1680,9 +1847,10 @@ pub fn parse_externs(
 
 fn parse_remap_path_prefix(
     matches: &getopts::Matches,
+    debugging_opts: &DebuggingOptions,
     error_format: ErrorOutputType,
 ) -> Vec<(PathBuf, PathBuf)> {
-    matches
+    let mut mapping: Vec<(PathBuf, PathBuf)> = matches
         .opt_strs("remap-path-prefix")
         .into_iter()
         .map(|remap| {
@@ -1697,7 +1865,13 @@ fn parse_remap_path_prefix(
                 ),
             }
         })
-        .collect()
+        .collect();
+    if let Some(to) = &debugging_opts.remap_cwd_prefix {
+        if let Ok(cwd) = std::env::current_dir() {
+            mapping.push((cwd, to.clone()));
+        }
+    }
+    mapping
 }
 
 pub fn build_session_options(matches: &getopts::Matches) -> Options {
@@ -1818,7 +1992,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     let crate_name = matches.opt_str("crate-name");
 
-    let remap_path_prefix = parse_remap_path_prefix(matches, error_format);
+    let remap_path_prefix = parse_remap_path_prefix(matches, &debugging_opts, error_format);
 
     let pretty = parse_pretty(matches, &debugging_opts, error_format);
 
@@ -2101,7 +2275,7 @@ crate mod dep_tracking {
     use rustc_feature::UnstableFeatures;
     use rustc_span::edition::Edition;
     use rustc_target::spec::{CodeModel, MergeFunctions, PanicStrategy, RelocModel};
-    use rustc_target::spec::{RelroLevel, TargetTriple, TlsModel};
+    use rustc_target::spec::{RelroLevel, SplitDebuginfo, StackProtector, TargetTriple, TlsModel};
     use std::collections::hash_map::DefaultHasher;
     use std::collections::BTreeMap;
     use std::hash::Hash;
@@ -2154,12 +2328,14 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(Option<TlsModel>);
     impl_dep_tracking_hash_via_hash!(Option<PanicStrategy>);
     impl_dep_tracking_hash_via_hash!(Option<RelroLevel>);
+    impl_dep_tracking_hash_via_hash!(Option<SplitDebuginfo>);
     impl_dep_tracking_hash_via_hash!(Option<lint::Level>);
     impl_dep_tracking_hash_via_hash!(Option<PathBuf>);
     impl_dep_tracking_hash_via_hash!(CrateType);
     impl_dep_tracking_hash_via_hash!(MergeFunctions);
     impl_dep_tracking_hash_via_hash!(PanicStrategy);
     impl_dep_tracking_hash_via_hash!(RelroLevel);
+    impl_dep_tracking_hash_via_hash!(StackProtector);
     impl_dep_tracking_hash_via_hash!(Passes);
     impl_dep_tracking_hash_via_hash!(OptLevel);
     impl_dep_tracking_hash_via_hash!(LtoCli);
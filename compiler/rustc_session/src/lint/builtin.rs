@@ -2647,6 +2647,91 @@ declare_lint! {
     };
 }
 
+declare_lint! {
+    /// The `rust_2021_incompatible_closure_captures` lint detects variables
+    /// that are moved into a closure as a whole today, but of which only some
+    /// fields will be captured in the 2021 edition, changing when the rest of
+    /// the variable is dropped.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![deny(rust_2021_incompatible_closure_captures)]
+    ///
+    /// struct FancyInteger(i32);
+    ///
+    /// impl Drop for FancyInteger {
+    ///     fn drop(&mut self) {
+    ///         println!("Just dropped {}", self.0);
+    ///     }
+    /// }
+    ///
+    /// struct Point { x: FancyInteger, y: FancyInteger }
+    ///
+    /// fn main() {
+    ///     let p = Point { x: FancyInteger(10), y: FancyInteger(20) };
+    ///
+    ///     let c = || {
+    ///         let x = p.x;
+    ///     };
+    ///
+    ///     c();
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// In the above example, `p.y` will be dropped at the end of `main` in
+    /// the 2021 edition instead of when the closure is dropped, since the
+    /// closure only moves `p.x`. Adding `let _ = &p;` to the closure body
+    /// makes it capture all of `p` in every edition.
+    pub RUST_2021_INCOMPATIBLE_CLOSURE_CAPTURES,
+    Allow,
+    "detects closures affected by the change to capturing disjoint fields in the 2021 edition",
+    @future_incompatible = FutureIncompatibleInfo {
+        reference: "issue #53488 <https://github.com/rust-lang/rust/issues/53488>",
+        edition: Some(Edition::Edition2021),
+    };
+}
+
+declare_lint! {
+    /// The `unexpected_cfgs` lint detects unexpected conditional compilation conditions.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs command line option)
+    /// #[cfg(widnows)]
+    /// fn foo() {}
+    /// ```
+    ///
+    /// when compiled with `-Z unstable-options --check-cfg 'names()'`,
+    /// produces:
+    ///
+    /// ```text
+    /// warning: unexpected `cfg` condition name
+    ///  --> lint_example.rs:1:7
+    ///   |
+    /// 1 | #[cfg(widnows)]
+    ///   |       ^^^^^^^
+    ///   |
+    ///   = note: `#[warn(unexpected_cfgs)]` on by default
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// This lint is only active when a `--check-cfg` option has been passed
+    /// to the compiler and triggers whenever an unknown condition name or value
+    /// is used. The known condition names include the names of the
+    /// configuration options set by the compiler itself, such as `target_os`
+    /// and `unix`, and `values()` additionally checks the values that the
+    /// built-in targets give to them.
+    pub UNEXPECTED_CFGS,
+    Warn,
+    "detects unexpected names and values in `#[cfg]` conditions",
+}
+
 declare_tool_lint! {
     pub rustc::INEFFECTIVE_UNSTABLE_TRAIT_IMPL,
     Deny,
@@ -2732,6 +2817,8 @@ declare_lint_pass! {
         CENUM_IMPL_DROP_CAST,
         CONST_EVALUATABLE_UNCHECKED,
         INEFFECTIVE_UNSTABLE_TRAIT_IMPL,
+        RUST_2021_INCOMPATIBLE_CLOSURE_CAPTURES,
+        UNEXPECTED_CFGS,
     ]
 }
 
@@ -21,12 +21,17 @@ use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitterWriter;
 use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId, ErrorReported};
 use rustc_span::edition::Edition;
-use rustc_span::source_map::{FileLoader, MultiSpan, RealFileLoader, SourceMap, Span};
+use rustc_span::source_map::{
+    FileLoader, FilePathMapping, MultiSpan, RealFileLoader, SourceMap, Span,
+};
 use rustc_span::{sym, SourceFileHashAlgorithm, Symbol};
 use rustc_target::asm::InlineAsmArch;
-use rustc_target::spec::{CodeModel, PanicStrategy, RelocModel, RelroLevel};
+use rustc_target::spec::{
+    CodeModel, PanicStrategy, RelocModel, RelroLevel, SplitDebuginfo, StackProtector,
+};
 use rustc_target::spec::{Target, TargetTriple, TlsModel};
 
 use std::cell::{self, RefCell};
@@ -297,6 +302,19 @@ impl Session {
     pub fn finish_diagnostics(&self, registry: &Registry) {
         self.check_miri_unleashed_features();
         self.diagnostic().print_error_count(registry);
+        self.emit_future_breakage();
+    }
+
+    fn emit_future_breakage(&self) {
+        if !self.opts.debugging_opts.emit_future_incompat_report {
+            return;
+        }
+
+        let diags = self.diagnostic().take_future_breakage_diagnostics();
+        if diags.is_empty() {
+            return;
+        }
+        self.diagnostic().emit_future_breakage_report(diags);
     }
 
     pub fn local_crate_disambiguator(&self) -> CrateDisambiguator {
@@ -323,6 +341,12 @@ impl Session {
         self.const_eval_limit.get().copied().unwrap()
     }
 
+    pub fn struct_span_allow<S: Into<MultiSpan>>(&self, sp: S, msg: &str) -> DiagnosticBuilder<'_> {
+        self.diagnostic().struct_span_allow(sp, msg)
+    }
+    pub fn struct_allow(&self, msg: &str) -> DiagnosticBuilder<'_> {
+        self.diagnostic().struct_allow(msg)
+    }
     pub fn struct_span_warn<S: Into<MultiSpan>>(&self, sp: S, msg: &str) -> DiagnosticBuilder<'_> {
         self.diagnostic().struct_span_warn(sp, msg)
     }
@@ -733,6 +757,20 @@ impl Session {
         self.opts.cg.relocation_model.unwrap_or(self.target.target.options.relocation_model)
     }
 
+    pub fn split_debuginfo(&self) -> SplitDebuginfo {
+        self.opts.cg.split_debuginfo.unwrap_or(self.target.target.options.split_debuginfo)
+    }
+
+    /// Returns `true` if LLVM splits debuginfo out of the object files into
+    /// `.dwo` files, which is how `-C split-debuginfo` is implemented on
+    /// targets using DWARF. macOS and Windows split debuginfo when linking.
+    pub fn split_dwarf(&self) -> bool {
+        self.split_debuginfo() != SplitDebuginfo::Off
+            && self.opts.debuginfo != config::DebugInfo::None
+            && !self.target.target.options.is_like_osx
+            && !self.target.target.options.is_like_windows
+    }
+
     pub fn code_model(&self) -> Option<CodeModel> {
         self.opts.cg.code_model.or(self.target.target.options.code_model)
     }
@@ -1021,6 +1059,11 @@ impl Session {
         self.opts.edition >= Edition::Edition2018
     }
 
+    /// Are we allowed to use features from the Rust 2021 edition?
+    pub fn rust_2021(&self) -> bool {
+        self.opts.edition >= Edition::Edition2021
+    }
+
     pub fn edition(&self) -> Edition {
         self.opts.edition
     }
@@ -1045,6 +1088,16 @@ impl Session {
         dbg_opts.plt.unwrap_or(needs_plt || !full_relro)
     }
 
+    /// Returns the stack protector strategy to use, which is always
+    /// `StackProtector::None` on targets that do not support stack canaries.
+    pub fn stack_protector(&self) -> StackProtector {
+        if self.target.target.options.supports_stack_protector {
+            self.opts.debugging_opts.stack_protector
+        } else {
+            StackProtector::None
+        }
+    }
+
     /// Checks if LLVM lifetime markers should be emitted.
     pub fn emit_lifetime_markers(&self) -> bool {
         self.opts.optimize != config::OptLevel::No
@@ -1216,6 +1269,8 @@ fn default_emitter(
             )
             .ui_testing(sopts.debugging_opts.ui_testing),
         ),
+        (config::ErrorOutputType::Sarif, None) => Box::new(SarifEmitter::stderr(source_map)),
+        (config::ErrorOutputType::Sarif, Some(dst)) => Box::new(SarifEmitter::new(dst, source_map)),
     }
 }
 
@@ -1474,6 +1529,32 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    // Only the target's default way of splitting debuginfo is known to work
+    // everywhere, the other modes depend on tooling of the platform (e.g.
+    // `llvm-dwp` for packed DWARF) so they're unstable for now. macOS has
+    // supported both `dsymutil` and scattered object files for a long time.
+    let split_debuginfo = sess.split_debuginfo();
+    if split_debuginfo != sess.target.target.options.split_debuginfo
+        && !sess.target.target.options.is_like_osx
+        && !sess.opts.debugging_opts.unstable_options
+    {
+        sess.err(&format!(
+            "`-C split-debuginfo={}` is unstable on this platform, pass \
+             `-Z unstable-options` to enable it",
+            split_debuginfo.desc()
+        ));
+    }
+
+    if sess.opts.debugging_opts.stack_protector != StackProtector::None
+        && !sess.target.target.options.supports_stack_protector
+    {
+        sess.warn(&format!(
+            "`-Z stack-protector={}` is not supported for target {} and will be ignored",
+            sess.opts.debugging_opts.stack_protector.desc(),
+            sess.opts.target_triple
+        ));
+    }
+
     // PGO does not work reliably with panic=unwind on Windows. Let's make it
     // an error to combine the two for now. It always runs into an assertions
     // if LLVM is built with assertions, but without assertions it sometimes
@@ -1567,6 +1648,9 @@ pub fn early_error(output: config::ErrorOutputType, msg: &str) -> ! {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, false))
         }
+        config::ErrorOutputType::Sarif => {
+            Box::new(SarifEmitter::stderr(Lrc::new(SourceMap::new(FilePathMapping::empty()))))
+        }
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_fatal(msg).emit();
@@ -1582,6 +1666,9 @@ pub fn early_warn(output: config::ErrorOutputType, msg: &str) {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, false))
         }
+        config::ErrorOutputType::Sarif => {
+            Box::new(SarifEmitter::stderr(Lrc::new(SourceMap::new(FilePathMapping::empty()))))
+        }
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_warn(msg).emit();
@@ -144,6 +144,7 @@ symbols! {
         FormatSpec,
         Formatter,
         From,
+        FromIterator,
         Future,
         FxHashMap,
         FxHashSet,
@@ -196,6 +197,8 @@ symbols! {
         Sync,
         Target,
         Try,
+        TryFrom,
+        TryInto,
         Ty,
         TyCtxt,
         TyKind,
@@ -282,6 +285,7 @@ symbols! {
         attr_literals,
         attributes,
         augmented_assignments,
+        auto_cfg,
         automatically_derived,
         avx512_target_feature,
         await_macro,
@@ -315,6 +319,7 @@ symbols! {
         call_mut,
         call_once,
         caller_location,
+        capture_disjoint_fields,
         cdylib,
         ceilf32,
         ceilf64,
@@ -323,6 +328,7 @@ symbols! {
         cfg_attr,
         cfg_attr_multi,
         cfg_doctest,
+        cfg_hide,
         cfg_sanitize,
         cfg_target_feature,
         cfg_target_has_atomic,
@@ -360,6 +366,7 @@ symbols! {
         const_fn_transmute,
         const_fn_union,
         const_generics,
+        const_generics_defaults,
         const_if_match,
         const_impl_trait,
         const_in_array_repeat_expressions,
@@ -430,6 +437,7 @@ symbols! {
         deref_mut,
         deref_target,
         derive,
+        destructuring_assignment,
         diagnostic,
         direct,
         discriminant_kind,
@@ -533,6 +541,7 @@ symbols! {
         from_desugaring,
         from_error,
         from_generator,
+        from_iter,
         from_method,
         from_ok,
         from_size_align_unchecked,
@@ -618,6 +627,7 @@ symbols! {
         lazy_normalization_consts,
         le,
         let_chains,
+        let_else,
         lhs,
         lib,
         libc,
@@ -684,6 +694,7 @@ symbols! {
         minnumf32,
         minnumf64,
         mips_target_feature,
+        miri,
         misc,
         module,
         module_path,
@@ -700,6 +711,7 @@ symbols! {
         naked,
         naked_functions,
         name,
+        names,
         ne,
         nearbyintf32,
         nearbyintf64,
@@ -810,6 +822,7 @@ symbols! {
         prefetch_write_data,
         prefetch_write_instruction,
         prelude,
+        prelude_2021,
         prelude_import,
         preserves_flags,
         primitive,
@@ -883,6 +896,8 @@ symbols! {
         rust,
         rust_2015_preview,
         rust_2018_preview,
+        rust_2021,
+        rust_2021_preview,
         rust_begin_unwind,
         rust_eh_catch_typeinfo,
         rust_eh_personality,
@@ -1095,6 +1110,7 @@ symbols! {
         test_case,
         test_removed_feature,
         test_runner,
+        test_timeout,
         then_with,
         thread,
         thread_local,
@@ -1111,6 +1127,8 @@ symbols! {
         truncf32,
         truncf64,
         try_blocks,
+        try_from,
+        try_into,
         try_trait,
         tt,
         tuple,
@@ -1176,6 +1194,7 @@ symbols! {
         va_list,
         va_start,
         val,
+        values,
         var,
         variant_count,
         vec,
@@ -382,6 +382,110 @@ impl ToJson for TlsModel {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Encodable, Decodable)]
+pub enum SplitDebuginfo {
+    /// Split debug-information is disabled, meaning that on supported platforms
+    /// you can find all debug information in the executable itself. This is
+    /// only supported for ELF effectively.
+    ///
+    /// * Windows - not supported
+    /// * macOS - don't run `dsymutil`
+    /// * ELF - `.dwarf_*` sections
+    Off,
+
+    /// Split debug-information can be found in a "packed" location separate
+    /// from the final artifact. This is supported on all platforms.
+    ///
+    /// * Windows - `*.pdb`
+    /// * macOS - `*.dSYM` (run `dsymutil`)
+    /// * ELF - `*.dwp` (run `llvm-dwp`)
+    Packed,
+
+    /// Split debug-information can be found in individual object files on the
+    /// filesystem. The main executable may point to the object files.
+    ///
+    /// * Windows - not supported
+    /// * macOS - supported, scattered object files
+    /// * ELF - supported, scattered `*.dwo` files
+    Unpacked,
+}
+
+impl SplitDebuginfo {
+    pub fn desc(&self) -> &str {
+        match *self {
+            SplitDebuginfo::Off => "off",
+            SplitDebuginfo::Packed => "packed",
+            SplitDebuginfo::Unpacked => "unpacked",
+        }
+    }
+}
+
+impl FromStr for SplitDebuginfo {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<SplitDebuginfo, ()> {
+        match s {
+            "off" => Ok(SplitDebuginfo::Off),
+            "packed" => Ok(SplitDebuginfo::Packed),
+            "unpacked" => Ok(SplitDebuginfo::Unpacked),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ToJson for SplitDebuginfo {
+    fn to_json(&self) -> Json {
+        self.desc().to_json()
+    }
+}
+
+/// The stack smash protection strategy requested with `-Z stack-protector`.
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Encodable, Decodable)]
+pub enum StackProtector {
+    /// Disable stack canary generation.
+    None,
+
+    /// On LLVM, mark all generated LLVM functions with the `ssp` attribute (see
+    /// llvm/docs/LangRef.rst). This triggers stack canary generation in
+    /// functions which contain an array of a byte-sized type with more than
+    /// eight elements.
+    Basic,
+
+    /// On LLVM, mark all generated LLVM functions with the `sspstrong`
+    /// attribute (see llvm/docs/LangRef.rst). This triggers stack canary
+    /// generation in functions which either contain an array, or which take
+    /// the address of a local variable.
+    Strong,
+
+    /// Generate stack canaries in all functions.
+    All,
+}
+
+impl StackProtector {
+    pub fn desc(&self) -> &str {
+        match *self {
+            StackProtector::None => "none",
+            StackProtector::Basic => "basic",
+            StackProtector::Strong => "strong",
+            StackProtector::All => "all",
+        }
+    }
+}
+
+impl FromStr for StackProtector {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<StackProtector, ()> {
+        match s {
+            "none" => Ok(StackProtector::None),
+            "basic" => Ok(StackProtector::Basic),
+            "strong" => Ok(StackProtector::Strong),
+            "all" => Ok(StackProtector::All),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Everything is flattened to a single enum to make the json encoding/decoding less annoying.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub enum LinkOutputKind {
@@ -848,6 +952,9 @@ pub struct TargetOptions {
     /// resolve all symbols at startup and marks the GOT read-only before
     /// starting the program, preventing overwriting the GOT.
     pub relro_level: RelroLevel,
+    /// How debug information is split from the final artifact by default, this
+    /// controls the default value of the `-C split-debuginfo` flag.
+    pub split_debuginfo: SplitDebuginfo,
     /// Format that archives should be emitted in. This affects whether we use
     /// LLVM to assemble an archive or fall back to the system linker, and
     /// currently only "gnu" is used to fall into LLVM. Unknown strings cause
@@ -898,6 +1005,11 @@ pub struct TargetOptions {
     /// Whether or not stack probes (__rust_probestack) are enabled
     pub stack_probes: bool,
 
+    /// Whether the target can emit stack canaries for `-Z stack-protector`,
+    /// i.e. whether its runtime provides `__stack_chk_guard` and `__stack_chk_fail`
+    /// or an equivalent.
+    pub supports_stack_protector: bool,
+
     /// The minimum alignment for global symbols.
     pub min_global_align: Option<u64>,
 
@@ -1024,6 +1136,7 @@ impl Default for TargetOptions {
             static_position_independent_executables: false,
             needs_plt: false,
             relro_level: RelroLevel::None,
+            split_debuginfo: SplitDebuginfo::Off,
             pre_link_objects: Default::default(),
             post_link_objects: Default::default(),
             pre_link_objects_fallback: Default::default(),
@@ -1050,6 +1163,7 @@ impl Default for TargetOptions {
             crt_static_default: false,
             crt_static_respected: false,
             stack_probes: false,
+            supports_stack_protector: true,
             min_global_align: None,
             default_codegen_units: None,
             trap_unreachable: true,
@@ -1258,6 +1372,19 @@ impl Target {
                     Some(Ok(()))
                 })).unwrap_or(Ok(()))
             } );
+            ($key_name:ident, SplitDebuginfo) => ( {
+                let name = (stringify!($key_name)).replace("_", "-");
+                obj.find(&name[..]).and_then(|o| o.as_string().and_then(|s| {
+                    match s.parse::<SplitDebuginfo>() {
+                        Ok(level) => base.options.$key_name = level,
+                        _ => return Some(Err(format!("'{}' is not a valid value for \
+                                                      split-debuginfo. Use 'off', 'packed', or \
+                                                      'unpacked'.",
+                                                      s))),
+                    }
+                    Some(Ok(()))
+                })).unwrap_or(Ok(()))
+            } );
             ($key_name:ident, list) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
                 if let Some(v) = obj.find(&name).and_then(Json::as_array) {
@@ -1439,6 +1566,7 @@ impl Target {
         key!(static_position_independent_executables, bool);
         key!(needs_plt, bool);
         key!(relro_level, RelroLevel)?;
+        key!(split_debuginfo, SplitDebuginfo)?;
         key!(archive_format);
         key!(allow_asm, bool);
         key!(main_needs_argc_argv, bool);
@@ -1454,6 +1582,7 @@ impl Target {
         key!(crt_static_default, bool);
         key!(crt_static_respected, bool);
         key!(stack_probes, bool);
+        key!(supports_stack_protector, bool);
         key!(min_global_align, Option<u64>);
         key!(default_codegen_units, Option<u64>);
         key!(trap_unreachable, bool);
@@ -1677,6 +1806,7 @@ impl ToJson for Target {
         target_option_val!(static_position_independent_executables);
         target_option_val!(needs_plt);
         target_option_val!(relro_level);
+        target_option_val!(split_debuginfo);
         target_option_val!(archive_format);
         target_option_val!(allow_asm);
         target_option_val!(main_needs_argc_argv);
@@ -1692,6 +1822,7 @@ impl ToJson for Target {
         target_option_val!(crt_static_default);
         target_option_val!(crt_static_respected);
         target_option_val!(stack_probes);
+        target_option_val!(supports_stack_protector);
         target_option_val!(min_global_align);
         target_option_val!(default_codegen_units);
         target_option_val!(trap_unreachable);
@@ -51,6 +51,10 @@ pub fn target() -> Target {
             // This behavior is not supported by PTX ISA.
             merge_functions: MergeFunctions::Disabled,
 
+            // There is no runtime to provide the stack canary and its failure
+            // handler, so `-Z stack-protector` is ignored.
+            supports_stack_protector: false,
+
             // FIXME: enable compilation tests for the target and
             // create the tests for this.
             unsupported_abis: vec![
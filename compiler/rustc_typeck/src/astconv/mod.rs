@@ -382,7 +382,18 @@ impl<'o, 'tcx> dyn AstConv<'tcx> + 'o {
                         self.ast_ty_to_ty(&ty).into()
                     }
                 }
-                (GenericParamDefKind::Const, GenericArg::Const(ct)) => {
+                (GenericParamDefKind::Const { has_default }, GenericArg::Const(ct)) => {
+                    if *has_default {
+                        tcx.check_optional_stability(
+                            param.def_id,
+                            Some(arg.id()),
+                            arg.span(),
+                            |_, _| {
+                                // Like for type parameters, default const parameters
+                                // may not be marked with stability attributes.
+                            },
+                        )
+                    }
                     ty::Const::from_opt_const_arg_anon_const(
                         tcx,
                         ty::WithOptConstParam {
@@ -432,10 +443,14 @@ impl<'o, 'tcx> dyn AstConv<'tcx> + 'o {
                             tcx.ty_error().into()
                         }
                     }
-                    GenericParamDefKind::Const => {
+                    GenericParamDefKind::Const { has_default } => {
                         let ty = tcx.at(span).type_of(param.def_id);
-                        // FIXME(const_generics:defaults)
-                        if infer_args {
+                        if !infer_args && has_default {
+                            // No const parameter provided, but a default exists. Defaults
+                            // cannot depend on other generic parameters, so there is
+                            // nothing to substitute.
+                            tcx.const_param_default(param.def_id).into()
+                        } else if infer_args {
                             // No const parameters were provided, we can infer all.
                             self.ct_infer(ty, Some(param), span).into()
                         } else {
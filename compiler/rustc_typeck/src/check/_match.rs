@@ -3,7 +3,7 @@ use crate::check::{Diverges, Expectation, FnCtxt, Needs};
 use rustc_hir::{self as hir, ExprKind};
 use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use rustc_infer::traits::Obligation;
-use rustc_middle::ty::{self, ToPredicate, Ty};
+use rustc_middle::ty::{self, ToPredicate, Ty, TypeFoldable};
 use rustc_span::Span;
 use rustc_trait_selection::opaque_types::InferCtxtExt as _;
 use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt;
@@ -109,6 +109,8 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 && self.if_fallback_coercion(expr.span, &arms[0].body, &mut coercion)
             {
                 tcx.ty_error()
+            } else if match_src == LetElseDesugar && i != 0 {
+                self.check_let_else_block(&arm.body)
             } else {
                 // Only call this if this is not an `if` expr with an expected type and no `else`
                 // clause to avoid duplicated type errors. (#60254)
@@ -241,6 +243,23 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         coercion.complete(self)
     }
 
+    /// Checks the `else` block of a `let...else`, which must diverge: the code following the
+    /// statement relies on the bindings of its pattern.
+    fn check_let_else_block(&self, els: &'tcx hir::Expr<'tcx>) -> Ty<'tcx> {
+        let ty = self.check_expr_with_expectation(els, Expectation::NoExpectation);
+        if self.diverges.get().is_always() || ty.references_error() {
+            return ty;
+        }
+        self.tcx
+            .sess
+            .struct_span_err(els.span, "`else` clause of `let...else` does not diverge")
+            .span_label(els.span, format!("expected `!`, found `{}`", ty))
+            .help("try adding a diverging expression, such as `return` or `panic!(..)`")
+            .help("or use `match` instead of `let...else`")
+            .emit();
+        self.tcx.ty_error()
+    }
+
     /// When the previously checked expression (the scrutinee) diverges,
     /// warn the user about the match arms being unreachable.
     fn warn_arms_when_scrutinee_diverges(
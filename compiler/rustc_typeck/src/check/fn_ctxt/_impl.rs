@@ -1285,7 +1285,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                     (GenericParamDefKind::Type { .. }, GenericArg::Type(ty)) => {
                         self.to_ty(ty).into()
                     }
-                    (GenericParamDefKind::Const, GenericArg::Const(ct)) => {
+                    (GenericParamDefKind::Const { .. }, GenericArg::Const(ct)) => {
                         self.const_arg_to_const(&ct.value, param.def_id).into()
                     }
                     _ => unreachable!(),
@@ -1315,10 +1315,15 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                                 self.var_for_def(span, param)
                             }
                         }
-                        GenericParamDefKind::Const => {
-                            // FIXME(const_generics:defaults)
-                            // No const parameters were provided, we have to infer them.
-                            self.var_for_def(span, param)
+                        GenericParamDefKind::Const { has_default } => {
+                            if !infer_args && has_default {
+                                // Like for types, we provide the default where any
+                                // const argument is missing.
+                                tcx.const_param_default(param.def_id).into()
+                            } else {
+                                // No const parameters were provided, we have to infer them.
+                                self.var_for_def(span, param)
+                            }
                         }
                     }
                 },
@@ -330,7 +330,7 @@ impl<'a, 'tcx> ConfirmContext<'a, 'tcx> {
                     AstConv::ast_region_to_region(self.fcx, lt, Some(param)).into()
                 }
                 (GenericParamDefKind::Type { .. }, GenericArg::Type(ty)) => self.to_ty(ty).into(),
-                (GenericParamDefKind::Const, GenericArg::Const(ct)) => {
+                (GenericParamDefKind::Const { .. }, GenericArg::Const(ct)) => {
                     self.const_arg_to_const(&ct.value, param.def_id).into()
                 }
                 _ => unreachable!(),
@@ -29,18 +29,33 @@
 //! `ty::InferBorrow(upvar_id)` or something like that, but this would
 //! then mean that all later passes would have to check for these figments
 //! and report an error, and it just seems like more mess in the end.)
+//!
+//! ### Capturing disjoint fields
+//!
+//! Besides the borrow kind of each upvar, we also track the precise places
+//! (e.g. `s.x` or `*r`) that the closure body uses. Each place is first
+//! truncated to a place that can actually be captured (see
+//! `restrict_capture_precision()`), and the places are then reduced to a
+//! minimal set in which no place is an ancestor of another (see
+//! `compute_min_captures()`). With the `capture_disjoint_fields` feature, or in
+//! the 2021 edition, the closure captures exactly that set of places. Otherwise
+//! it captures each variable as a whole, and the precise places are only used
+//! to lint about closures whose drop order would change with precise captures.
 
 use super::FnCtxt;
 
 use crate::expr_use_visitor as euv;
 use rustc_data_structures::fx::FxIndexMap;
+use rustc_errors::Applicability;
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::def_id::LocalDefId;
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_infer::infer::UpvarRegion;
-use rustc_middle::hir::place::{PlaceBase, PlaceWithHirId};
+use rustc_middle::hir::place::{Place, PlaceBase, PlaceWithHirId, Projection, ProjectionKind};
+use rustc_middle::ty::fold::TypeFoldable;
 use rustc_middle::ty::{self, Ty, TyCtxt, UpvarSubsts};
+use rustc_session::lint;
 use rustc_span::{Span, Symbol};
 use std::collections::hash_map::Entry;
 
@@ -156,6 +171,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             current_closure_kind: ty::ClosureKind::LATTICE_BOTTOM,
             current_origin: None,
             adjust_upvar_captures: ty::UpvarCaptureMap::default(),
+            capture_information: Default::default(),
         };
         euv::ExprUseVisitor::new(
             &mut delegate,
@@ -184,6 +200,21 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
 
         self.typeck_results.borrow_mut().upvar_capture_map.extend(delegate.adjust_upvar_captures);
 
+        let precise_captures =
+            self.compute_min_captures(closure_def_id, delegate.capture_information);
+        let min_captures = if enable_precise_capture(self.tcx, span) {
+            precise_captures
+        } else {
+            self.lint_capture_migrations(closure_hir_id, span, body, &precise_captures);
+            self.compute_root_captures(closure_def_id)
+        };
+        if !min_captures.is_empty() {
+            self.typeck_results
+                .borrow_mut()
+                .closure_min_captures
+                .insert(closure_def_id, min_captures);
+        }
+
         // Now that we've analyzed the closure, we know how each
         // variable is borrowed, and we know what traits the closure
         // implements (Fn vs FnMut etc). We now have some updates to do
@@ -196,15 +227,14 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         // C, then the type would have infinite size (and the
         // inference algorithm will reject it).
 
-        // Equate the type variables for the upvars with the actual types.
-        let final_upvar_tys = self.final_upvar_tys(closure_hir_id);
+        // Equate the type variable for the upvars with the actual types.
+        let final_upvar_tys = self.final_upvar_tys(closure_def_id);
         debug!(
             "analyze_closure: id={:?} substs={:?} final_upvar_tys={:?}",
             closure_hir_id, substs, final_upvar_tys
         );
-        for (upvar_ty, final_upvar_ty) in substs.upvar_tys().zip(final_upvar_tys) {
-            self.demand_suptype(span, upvar_ty, final_upvar_ty);
-        }
+        let final_tupled_upvars_type = self.tcx.mk_tup(final_upvar_tys.iter());
+        self.demand_suptype(span, substs.tupled_upvars_ty(), final_tupled_upvars_type);
 
         // If we are also inferred the closure kind here,
         // process any deferred resolutions.
@@ -214,40 +244,324 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         }
     }
 
-    // Returns a list of `Ty`s for each upvar.
-    fn final_upvar_tys(&self, closure_id: hir::HirId) -> Vec<Ty<'tcx>> {
+    // Returns a list of `Ty`s for each captured place, in the order in which
+    // they are stored in the closure environment.
+    fn final_upvar_tys(&self, closure_def_id: DefId) -> Vec<Ty<'tcx>> {
         // Presently an unboxed closure type cannot "escape" out of a
         // function, so we will only encounter ones that originated in the
         // local crate or were inlined into it along with some function.
         // This may change if abstract return types of some sort are
         // implemented.
         let tcx = self.tcx;
-        let closure_def_id = tcx.hir().local_def_id(closure_id);
 
-        tcx.upvars_mentioned(closure_def_id)
-            .iter()
-            .flat_map(|upvars| {
-                upvars.iter().map(|(&var_hir_id, _)| {
-                    let upvar_ty = self.node_ty(var_hir_id);
-                    let upvar_id = ty::UpvarId {
-                        var_path: ty::UpvarPath { hir_id: var_hir_id },
-                        closure_expr_id: closure_def_id,
-                    };
-                    let capture = self.typeck_results.borrow().upvar_capture(upvar_id);
+        self.typeck_results
+            .borrow()
+            .closure_min_captures_flattened(closure_def_id)
+            .map(|captured_place| {
+                let upvar_ty = captured_place.place.ty();
+                let capture = captured_place.info.capture_kind;
 
-                    debug!("var_id={:?} upvar_ty={:?} capture={:?}", var_hir_id, upvar_ty, capture);
+                debug!(
+                    "place={:?} upvar_ty={:?} capture={:?}",
+                    captured_place.place, upvar_ty, capture
+                );
 
-                    match capture {
-                        ty::UpvarCapture::ByValue(_) => upvar_ty,
-                        ty::UpvarCapture::ByRef(borrow) => tcx.mk_ref(
-                            borrow.region,
-                            ty::TypeAndMut { ty: upvar_ty, mutbl: borrow.kind.to_mutbl_lossy() },
-                        ),
-                    }
-                })
+                match capture {
+                    ty::UpvarCapture::ByValue(_) => upvar_ty,
+                    ty::UpvarCapture::ByRef(borrow) => tcx.mk_ref(
+                        borrow.region,
+                        ty::TypeAndMut { ty: upvar_ty, mutbl: borrow.kind.to_mutbl_lossy() },
+                    ),
+                }
             })
             .collect()
     }
+
+    /// Reduces the places used by the closure `closure_def_id` to the minimal set of places
+    /// that it has to capture, grouped by root variable. The root variables are in the order in
+    /// which they are mentioned in the closure, and no captured place is an ancestor of another
+    /// one. The capture kind of each place is the strongest kind needed by any of the places it
+    /// covers.
+    ///
+    /// A variable that is mentioned in the closure but none of whose places are used, such as
+    /// `x` in `let _ = x;`, is captured as a whole.
+    fn compute_min_captures(
+        &self,
+        closure_def_id: DefId,
+        capture_information: FxIndexMap<Place<'tcx>, ty::CaptureInfo<'tcx>>,
+    ) -> ty::RootVariableMinCaptureList<'tcx> {
+        let mut root_var_min_capture_list = ty::RootVariableMinCaptureList::default();
+        let upvars = match self.tcx.upvars_mentioned(closure_def_id) {
+            Some(upvars) => upvars,
+            None => return root_var_min_capture_list,
+        };
+        for &var_hir_id in upvars.keys() {
+            root_var_min_capture_list.insert(var_hir_id, ty::MinCaptureList::new());
+        }
+
+        for (place, mut capture_info) in capture_information {
+            let var_hir_id = match place.base {
+                PlaceBase::Upvar(upvar_id) => upvar_id.var_path.hir_id,
+                base => bug!("expected upvar, found={:?}", base),
+            };
+            let min_cap_list = match root_var_min_capture_list.get_mut(&var_hir_id) {
+                Some(min_cap_list) => min_cap_list,
+                None => bug!("captured variable {:?} is not mentioned", var_hir_id),
+            };
+
+            // If an ancestor of the place is already captured, it also covers this place.
+            if let Some(ancestor) = min_cap_list.iter_mut().find(|possible_ancestor| {
+                is_ancestor_or_same_capture(&possible_ancestor.place, &place)
+            }) {
+                let capture_kind = truncate_capture_kind(
+                    &place,
+                    ancestor.place.projections.len(),
+                    capture_info.capture_kind,
+                );
+                ancestor.info = determine_capture_info(
+                    ancestor.info,
+                    ty::CaptureInfo { capture_kind, ..capture_info },
+                );
+                continue;
+            }
+
+            // Otherwise the place replaces all of its descendants that are already captured.
+            min_cap_list.retain(|descendant| {
+                if !is_ancestor_or_same_capture(&place, &descendant.place) {
+                    return true;
+                }
+                let capture_kind = truncate_capture_kind(
+                    &descendant.place,
+                    place.projections.len(),
+                    descendant.info.capture_kind,
+                );
+                capture_info = determine_capture_info(
+                    capture_info,
+                    ty::CaptureInfo { capture_kind, ..descendant.info },
+                );
+                false
+            });
+
+            let mutability = self.determine_capture_mutability(&place);
+            min_cap_list.push(ty::CapturedPlace { place, info: capture_info, mutability });
+        }
+
+        for (&var_hir_id, min_cap_list) in root_var_min_capture_list.iter_mut() {
+            if min_cap_list.is_empty() {
+                let upvar_id = ty::UpvarId {
+                    var_path: ty::UpvarPath { hir_id: var_hir_id },
+                    closure_expr_id: closure_def_id.expect_local(),
+                };
+                min_cap_list.push(self.root_variable_capture(upvar_id));
+            }
+        }
+
+        debug!(
+            "compute_min_captures({:?}): min_captures={:?}",
+            closure_def_id, root_var_min_capture_list
+        );
+        root_var_min_capture_list
+    }
+
+    /// Returns the captures of a closure that captures each variable it mentions as a whole.
+    fn compute_root_captures(&self, closure_def_id: DefId) -> ty::RootVariableMinCaptureList<'tcx> {
+        let mut root_var_min_capture_list = ty::RootVariableMinCaptureList::default();
+        if let Some(upvars) = self.tcx.upvars_mentioned(closure_def_id) {
+            for &var_hir_id in upvars.keys() {
+                let upvar_id = ty::UpvarId {
+                    var_path: ty::UpvarPath { hir_id: var_hir_id },
+                    closure_expr_id: closure_def_id.expect_local(),
+                };
+                root_var_min_capture_list
+                    .insert(var_hir_id, vec![self.root_variable_capture(upvar_id)]);
+            }
+        }
+        root_var_min_capture_list
+    }
+
+    /// Returns the capture of the whole variable of `upvar_id`, with the capture kind inferred
+    /// for the variable.
+    fn root_variable_capture(&self, upvar_id: ty::UpvarId) -> ty::CapturedPlace<'tcx> {
+        let place = Place {
+            base_ty: self.node_ty(upvar_id.var_path.hir_id),
+            base: PlaceBase::Upvar(upvar_id),
+            projections: Vec::new(),
+        };
+        let capture_kind = self.typeck_results.borrow().upvar_capture(upvar_id);
+        let mutability = self.determine_capture_mutability(&place);
+        ty::CapturedPlace {
+            place,
+            info: ty::CaptureInfo { expr_id: None, capture_kind },
+            mutability,
+        }
+    }
+
+    /// A captured place is mutable if
+    /// 1. Projections don't include a Deref of an immut-borrow, **and**
+    /// 2. PlaceBase is mut or projections include a Deref of a mut-borrow.
+    fn determine_capture_mutability(&self, place: &Place<'tcx>) -> hir::Mutability {
+        let var_hir_id = match place.base {
+            PlaceBase::Upvar(upvar_id) => upvar_id.var_path.hir_id,
+            _ => unreachable!(),
+        };
+
+        let bm = self.typeck_results.borrow().extract_binding_mode(
+            self.tcx.sess,
+            var_hir_id,
+            self.tcx.hir().span(var_hir_id),
+        );
+
+        let mut is_mutbl = match bm {
+            Some(ty::BindByValue(mutability)) => mutability,
+            Some(ty::BindByReference(_)) | None => hir::Mutability::Not,
+        };
+
+        for pointer_ty in place.deref_tys() {
+            match pointer_ty.kind() {
+                // We don't capture derefs of raw pointers.
+                ty::RawPtr(_) => unreachable!(),
+
+                // Dereferencing a mut-ref allows us to mutate the place if we don't deref
+                // an immut-ref after on top of this.
+                ty::Ref(.., hir::Mutability::Mut) => is_mutbl = hir::Mutability::Mut,
+
+                // The place isn't mutable once we dereference an immutable reference.
+                ty::Ref(.., hir::Mutability::Not) => return hir::Mutability::Not,
+
+                // Dereferencing a box doesn't change mutability.
+                ty::Adt(def, ..) if def.is_box() => {}
+
+                unexpected_ty => bug!("deref of unexpected pointer type {:?}", unexpected_ty),
+            }
+        }
+
+        is_mutbl
+    }
+
+    /// Returns whether a value of type `ty`, of which only the places reached through
+    /// `captured_projections` are moved into the closure, has parts that need dropping outside of
+    /// those places.
+    fn has_significant_drop_outside_of_captures(
+        &self,
+        ty: Ty<'tcx>,
+        captured_projections: &[&[Projection<'tcx>]],
+    ) -> bool {
+        // A place that is captured as a whole is dropped along with the closure.
+        if captured_projections.iter().any(|projections| projections.is_empty()) {
+            return false;
+        }
+        if !ty.needs_drop(self.tcx, self.param_env) {
+            return false;
+        }
+        if captured_projections.is_empty() {
+            return true;
+        }
+
+        // Only the fields of tuples and structs without a destructor can be moved separately.
+        let field_tys = match ty.kind() {
+            ty::Tuple(..) => ty.tuple_fields().collect::<Vec<_>>(),
+            ty::Adt(def, substs) if def.is_struct() && !def.has_dtor(self.tcx) => def
+                .non_enum_variant()
+                .fields
+                .iter()
+                .map(|field| field.ty(self.tcx, substs))
+                .collect(),
+            _ => return true,
+        };
+        field_tys.into_iter().enumerate().any(|(i, field_ty)| {
+            let field_projections = captured_projections
+                .iter()
+                .filter_map(|projections| match projections[0].kind {
+                    ProjectionKind::Field(field, _) if field as usize == i => {
+                        Some(&projections[1..])
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            self.has_significant_drop_outside_of_captures(field_ty, &field_projections)
+        })
+    }
+
+    /// Lints a closure that captures whole variables if, with precise captures, a variable that
+    /// it moves would only be moved in part, so that the rest of it would be dropped at the end
+    /// of the enclosing scope rather than along with the closure.
+    fn lint_capture_migrations(
+        &self,
+        closure_hir_id: hir::HirId,
+        closure_span: Span,
+        body: &hir::Body<'_>,
+        precise_captures: &ty::RootVariableMinCaptureList<'tcx>,
+    ) {
+        let closure_def_id = self.tcx.hir().local_def_id(closure_hir_id);
+        let mut need_migrations = Vec::new();
+        for (&var_hir_id, min_cap_list) in precise_captures.iter() {
+            let upvar_id = ty::UpvarId {
+                var_path: ty::UpvarPath { hir_id: var_hir_id },
+                closure_expr_id: closure_def_id,
+            };
+            // Only variables moved into the closure are dropped along with it.
+            match self.typeck_results.borrow().upvar_capture(upvar_id) {
+                ty::UpvarCapture::ByValue(_) => {}
+                ty::UpvarCapture::ByRef(_) => continue,
+            }
+            let var_ty = self.resolve_vars_if_possible(&self.node_ty(var_hir_id));
+            if var_ty.has_infer_types_or_consts() {
+                continue;
+            }
+            let captured_projections = min_cap_list
+                .iter()
+                .map(|captured_place| &captured_place.place.projections[..])
+                .collect::<Vec<_>>();
+            if !self.has_significant_drop_outside_of_captures(var_ty, &captured_projections) {
+                continue;
+            }
+            need_migrations.push(var_hir_id);
+        }
+
+        if need_migrations.is_empty() {
+            return;
+        }
+
+        let closure_head_span = self.tcx.sess.source_map().guess_head_span(closure_span);
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::RUST_2021_INCOMPATIBLE_CLOSURE_CAPTURES,
+            closure_hir_id,
+            closure_head_span,
+            |lint| {
+                let mut diag = lint.build("drop order will change in Rust 2021");
+                let names = need_migrations
+                    .iter()
+                    .map(|&var_hir_id| format!("`{}`", var_name(self.tcx, var_hir_id)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let migration_string = match &need_migrations[..] {
+                    [var_hir_id] => format!("let _ = &{};", var_name(self.tcx, *var_hir_id)),
+                    vars => format!(
+                        "let _ = ({});",
+                        vars.iter()
+                            .map(|&var_hir_id| format!("&{}", var_name(self.tcx, var_hir_id)))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                };
+                let msg = format!("add a dummy let to cause {} to be fully captured", names);
+                match self.tcx.sess.source_map().span_to_snippet(body.value.span) {
+                    Ok(body_snippet) => {
+                        diag.span_suggestion(
+                            body.value.span,
+                            &msg,
+                            format!("{{ {} {} }}", migration_string, body_snippet),
+                            Applicability::MachineApplicable,
+                        );
+                    }
+                    Err(_) => {
+                        diag.help(&msg);
+                    }
+                }
+                diag.emit();
+            },
+        );
+    }
 }
 
 struct InferBorrowKind<'a, 'tcx> {
@@ -271,6 +585,12 @@ struct InferBorrowKind<'a, 'tcx> {
     // For each upvar that we access, we track the minimal kind of
     // access we need (ref, ref mut, move, etc).
     adjust_upvar_captures: ty::UpvarCaptureMap<'tcx>,
+
+    // The places based on upvars that are used in the closure body, truncated
+    // to the places that can be captured, along with the strongest kind of
+    // capture each of them needs. These are reduced to the places that are
+    // actually captured by `compute_min_captures`.
+    capture_information: FxIndexMap<Place<'tcx>, ty::CaptureInfo<'tcx>>,
 }
 
 impl<'a, 'tcx> InferBorrowKind<'a, 'tcx> {
@@ -454,6 +774,45 @@ impl<'a, 'tcx> InferBorrowKind<'a, 'tcx> {
         }
     }
 
+    /// Returns the kind of capture for a place of the upvar `upvar_id` that is used through
+    /// a borrow of kind `kind`. Places of `move` closures are always captured by value.
+    fn capture_kind_for_borrow(
+        &self,
+        upvar_id: ty::UpvarId,
+        kind: ty::BorrowKind,
+    ) -> ty::UpvarCapture<'tcx> {
+        match self.fcx.typeck_results.borrow().upvar_capture(upvar_id) {
+            ty::UpvarCapture::ByValue(_) => ty::UpvarCapture::ByValue(None),
+            ty::UpvarCapture::ByRef(upvar_borrow) => {
+                ty::UpvarCapture::ByRef(ty::UpvarBorrow { kind, region: upvar_borrow.region })
+            }
+        }
+    }
+
+    /// Records that the closure body uses `place_with_id` in a way that needs `capture_kind`,
+    /// if the place is based on one of the closure's upvars.
+    fn insert_capture(
+        &mut self,
+        place_with_id: &PlaceWithHirId<'tcx>,
+        capture_kind: ty::UpvarCapture<'tcx>,
+    ) {
+        match place_with_id.place.base {
+            PlaceBase::Upvar(upvar_id)
+                if upvar_id.closure_expr_id.to_def_id() == self.closure_def_id => {}
+            _ => return,
+        }
+
+        let (place, capture_kind) =
+            restrict_capture_precision(self.fcx.tcx, place_with_id.place.clone(), capture_kind);
+        let capture_info = ty::CaptureInfo { expr_id: Some(place_with_id.hir_id), capture_kind };
+        let capture_info = match self.capture_information.get(&place) {
+            Some(&existing) => determine_capture_info(existing, capture_info),
+            None => capture_info,
+        };
+        debug!("insert_capture: place={:?} capture_info={:?}", place, capture_info);
+        self.capture_information.insert(place, capture_info);
+    }
+
     fn adjust_closure_kind(
         &mut self,
         closure_id: LocalDefId,
@@ -501,6 +860,21 @@ impl<'a, 'tcx> euv::Delegate<'tcx> for InferBorrowKind<'a, 'tcx> {
     fn consume(&mut self, place_with_id: &PlaceWithHirId<'tcx>, mode: euv::ConsumeMode) {
         debug!("consume(place_with_id={:?},mode={:?})", place_with_id, mode);
         self.adjust_upvar_borrow_kind_for_consume(place_with_id, mode);
+
+        if let PlaceBase::Upvar(upvar_id) = place_with_id.place.base {
+            let capture_kind = match mode {
+                euv::Copy => self.capture_kind_for_borrow(upvar_id, ty::ImmBorrow),
+                euv::Move => {
+                    // As for the whole upvar, don't use the span of a pattern.
+                    let by_value_span = match self.fcx.tcx.hir().get(place_with_id.hir_id) {
+                        hir::Node::Pat(_) => None,
+                        _ => Some(self.fcx.tcx.hir().span(place_with_id.hir_id)),
+                    };
+                    ty::UpvarCapture::ByValue(by_value_span)
+                }
+            };
+            self.insert_capture(place_with_id, capture_kind);
+        }
     }
 
     fn borrow(&mut self, place_with_id: &PlaceWithHirId<'tcx>, bk: ty::BorrowKind) {
@@ -515,13 +889,156 @@ impl<'a, 'tcx> euv::Delegate<'tcx> for InferBorrowKind<'a, 'tcx> {
                 self.adjust_upvar_borrow_kind_for_mut(place_with_id);
             }
         }
+
+        if let PlaceBase::Upvar(upvar_id) = place_with_id.place.base {
+            let capture_kind = self.capture_kind_for_borrow(upvar_id, bk);
+            self.insert_capture(place_with_id, capture_kind);
+        }
     }
 
     fn mutate(&mut self, assignee_place: &PlaceWithHirId<'tcx>) {
         debug!("mutate(assignee_place={:?})", assignee_place);
 
         self.adjust_upvar_borrow_kind_for_mut(assignee_place);
+
+        if let PlaceBase::Upvar(upvar_id) = assignee_place.place.base {
+            let capture_kind = self.capture_kind_for_borrow(upvar_id, ty::MutBorrow);
+            self.insert_capture(assignee_place, capture_kind);
+        }
     }
+
+    fn fake_read(&mut self, place_with_id: &PlaceWithHirId<'tcx>) {
+        debug!("fake_read(place_with_id={:?})", place_with_id);
+
+        // MIR building needs a place for the initializer of a `let`, even if its pattern
+        // doesn't use all of it, so the place has to be captured.
+        if let PlaceBase::Upvar(upvar_id) = place_with_id.place.base {
+            let capture_kind = self.capture_kind_for_borrow(upvar_id, ty::ImmBorrow);
+            self.insert_capture(place_with_id, capture_kind);
+        }
+    }
+}
+
+/// Returns whether closures defined at `span` capture disjoint fields (RFC 2229) rather than
+/// whole variables.
+fn enable_precise_capture(tcx: TyCtxt<'_>, span: Span) -> bool {
+    tcx.features().capture_disjoint_fields || span.rust_2021()
+}
+
+/// Truncates `place` to a place that a closure can capture, and adjusts `capture_kind` to what
+/// is needed to capture the truncated place:
+///
+/// - A place is not captured past an index or a subslice, nor past a field of an enum, a union
+///   or a `repr(packed)` struct.
+/// - A place is not captured through a raw pointer; the pointer is captured instead.
+/// - A place that is captured by value is not captured past a dereference, nor past a field
+///   of a type that implements `Drop`, since it could not be moved out of.
+fn restrict_capture_precision<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    mut place: Place<'tcx>,
+    capture_kind: ty::UpvarCapture<'tcx>,
+) -> (Place<'tcx>, ty::UpvarCapture<'tcx>) {
+    let by_value = matches!(capture_kind, ty::UpvarCapture::ByValue(_));
+
+    let len = place.projections.iter().enumerate().position(|(i, proj)| {
+        let base_ty = place.ty_before_projection(i);
+        match proj.kind {
+            ProjectionKind::Index | ProjectionKind::Subslice => true,
+            ProjectionKind::Deref => by_value || base_ty.is_unsafe_ptr(),
+            ProjectionKind::Field(..) => match base_ty.kind() {
+                ty::Adt(def, _) => {
+                    def.is_enum()
+                        || def.is_union()
+                        || def.repr.packed()
+                        || (by_value && def.has_dtor(tcx))
+                }
+                _ => false,
+            },
+        }
+    });
+
+    match len {
+        Some(len) => {
+            let capture_kind = truncate_capture_kind(&place, len, capture_kind);
+            place.projections.truncate(len);
+            (place, capture_kind)
+        }
+        None => (place, capture_kind),
+    }
+}
+
+/// Returns the capture kind needed to capture the first `len` projections of `place`, when
+/// `capture_kind` is needed to capture all of `place`.
+///
+/// Accessing the rest of the place through a reference or raw pointer only needs the pointer
+/// to be read, or to be unique in the case of a mutable access through an `&mut` reference.
+fn truncate_capture_kind<'tcx>(
+    place: &Place<'tcx>,
+    len: usize,
+    mut capture_kind: ty::UpvarCapture<'tcx>,
+) -> ty::UpvarCapture<'tcx> {
+    if let ty::UpvarCapture::ByRef(ref mut upvar_borrow) = capture_kind {
+        for (i, proj) in place.projections.iter().enumerate().skip(len) {
+            if proj.kind != ProjectionKind::Deref {
+                continue;
+            }
+            let needed_kind = match place.ty_before_projection(i).kind() {
+                ty::Ref(.., hir::Mutability::Mut) => ty::UniqueImmBorrow,
+                ty::Ref(.., hir::Mutability::Not) | ty::RawPtr(_) => ty::ImmBorrow,
+                _ => continue,
+            };
+            upvar_borrow.kind = match (upvar_borrow.kind, needed_kind) {
+                (ty::ImmBorrow, _) | (_, ty::ImmBorrow) => ty::ImmBorrow,
+                (ty::UniqueImmBorrow, _) | (_, ty::UniqueImmBorrow) => ty::UniqueImmBorrow,
+                (ty::MutBorrow, ty::MutBorrow) => ty::MutBorrow,
+            };
+        }
+    }
+    capture_kind
+}
+
+/// Returns the one of `a` and `b` that needs the stronger capture kind, following the lattice
+/// `ImmBorrow < UniqueImmBorrow < MutBorrow < ByValue`.
+///
+/// If both need the same kind, `a` is preferred, unless only `b` points to an expression that
+/// needs it. Since places are recorded in the order in which they appear in the closure body,
+/// passing the existing information as `a` keeps the earliest expression for diagnostics.
+fn determine_capture_info<'tcx>(
+    a: ty::CaptureInfo<'tcx>,
+    b: ty::CaptureInfo<'tcx>,
+) -> ty::CaptureInfo<'tcx> {
+    let rank = |capture_kind: ty::UpvarCapture<'_>| match capture_kind {
+        ty::UpvarCapture::ByRef(ty::UpvarBorrow { kind: ty::ImmBorrow, .. }) => 0,
+        ty::UpvarCapture::ByRef(ty::UpvarBorrow { kind: ty::UniqueImmBorrow, .. }) => 1,
+        ty::UpvarCapture::ByRef(ty::UpvarBorrow { kind: ty::MutBorrow, .. }) => 2,
+        ty::UpvarCapture::ByValue(_) => 3,
+    };
+
+    let (rank_a, rank_b) = (rank(a.capture_kind), rank(b.capture_kind));
+    if rank_b > rank_a {
+        return b;
+    }
+    if rank_b < rank_a {
+        return a;
+    }
+    match (a.capture_kind, b.capture_kind) {
+        (ty::UpvarCapture::ByValue(None), ty::UpvarCapture::ByValue(Some(_))) => b,
+        _ if a.expr_id.is_none() && b.expr_id.is_some() => b,
+        _ => a,
+    }
+}
+
+/// Returns whether `possible_ancestor` is `place` or one of its ancestors, i.e. whether both
+/// are based on the same variable and the projections of `possible_ancestor` are a prefix of
+/// those of `place`.
+fn is_ancestor_or_same_capture(possible_ancestor: &Place<'_>, place: &Place<'_>) -> bool {
+    possible_ancestor.base == place.base
+        && possible_ancestor.projections.len() <= place.projections.len()
+        && possible_ancestor
+            .projections
+            .iter()
+            .zip(place.projections.iter())
+            .all(|(ancestor_proj, proj)| ancestor_proj.kind == proj.kind)
 }
 
 fn var_name(tcx: TyCtxt<'_>, var_hir_id: hir::HirId) -> Symbol {
@@ -35,6 +35,10 @@ pub trait Delegate<'tcx> {
 
     // The path at `place_with_id` is being assigned to.
     fn mutate(&mut self, assignee_place: &PlaceWithHirId<'tcx>);
+
+    // The value found at `place` is inspected, e.g. by the pattern of a
+    // `let`, without necessarily being consumed or borrowed.
+    fn fake_read(&mut self, _place_with_id: &PlaceWithHirId<'tcx>) {}
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -348,6 +352,7 @@ impl<'a, 'tcx> ExprUseVisitor<'a, 'tcx> {
             // `walk_pat`:
             self.walk_expr(&expr);
             let init_place = return_if_err!(self.mc.cat_expr(&expr));
+            self.delegate.fake_read(&init_place);
             self.walk_irrefutable_pat(&init_place, &local.pat);
         }
     }
@@ -542,29 +547,42 @@ impl<'a, 'tcx> ExprUseVisitor<'a, 'tcx> {
         }));
     }
 
+    /// Reports the places captured by the closure `closure_expr` as being used by the current
+    /// body.
+    ///
+    /// The closure may capture only parts of a variable (e.g. `p.x`), in which case only those
+    /// parts are reported. A captured variable that is local to the current body is reported
+    /// with a `PlaceBase::Local` base, and one that is itself captured by the current body
+    /// with a `PlaceBase::Upvar` base whose `closure_expr_id` is the current body.
     fn walk_captures(&mut self, closure_expr: &hir::Expr<'_>, fn_decl_span: Span) {
         debug!("walk_captures({:?})", closure_expr);
 
         let closure_def_id = self.tcx().hir().local_def_id(closure_expr.hir_id);
-        if let Some(upvars) = self.tcx().upvars_mentioned(closure_def_id) {
-            for &var_id in upvars.keys() {
-                let upvar_id = ty::UpvarId {
-                    var_path: ty::UpvarPath { hir_id: var_id },
-                    closure_expr_id: closure_def_id,
-                };
-                let upvar_capture = self.mc.typeck_results.upvar_capture(upvar_id);
-                let captured_place = return_if_err!(self.cat_captured_var(
+        let min_captures =
+            match self.mc.typeck_results.closure_min_captures.get(&closure_def_id.to_def_id()) {
+                Some(min_captures) => min_captures,
+                None => return,
+            };
+        for (&var_id, min_list) in min_captures.iter() {
+            for captured_place in min_list {
+                let var_place = return_if_err!(self.cat_captured_var(
                     closure_expr.hir_id,
                     fn_decl_span,
                     var_id,
                 ));
-                match upvar_capture {
+                let place_with_id = PlaceWithHirId::new(
+                    var_place.hir_id,
+                    var_place.place.base_ty,
+                    var_place.place.base,
+                    captured_place.place.projections.clone(),
+                );
+                match captured_place.info.capture_kind {
                     ty::UpvarCapture::ByValue(_) => {
-                        let mode = copy_or_move(&self.mc, &captured_place);
-                        self.delegate.consume(&captured_place, mode);
+                        let mode = copy_or_move(&self.mc, &place_with_id);
+                        self.delegate.consume(&place_with_id, mode);
                     }
                     ty::UpvarCapture::ByRef(upvar_borrow) => {
-                        self.delegate.borrow(&captured_place, upvar_borrow.kind);
+                        self.delegate.borrow(&place_with_id, upvar_borrow.kind);
                     }
                 }
             }
@@ -83,6 +83,7 @@
 ///
 /// assert_eq!(c.0, vec![0, 1, 2, 3, 4]);
 /// ```
+#[rustc_diagnostic_item = "FromIterator"]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[rustc_on_unimplemented(
     message = "a value of type `{Self}` cannot be built from an iterator \
@@ -57,6 +57,7 @@
     test(no_crate_inject, attr(deny(warnings))),
     test(attr(allow(dead_code, deprecated, unused_variables, unused_mut)))
 )]
+#![doc(auto_cfg = false)]
 #![no_core]
 #![warn(deprecated_in_future)]
 #![warn(missing_docs)]
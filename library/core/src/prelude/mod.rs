@@ -3,3 +3,22 @@
 #![stable(feature = "core_prelude", since = "1.4.0")]
 
 pub mod v1;
+
+/// The 2021 edition of the core prelude.
+///
+/// This is the `v1` prelude together with the conversion and collection
+/// traits that only become part of the prelude in the 2021 edition.
+#[unstable(feature = "prelude_2021", issue = "none")]
+pub mod rust_2021 {
+    #[unstable(feature = "prelude_2021", issue = "none")]
+    #[doc(no_inline)]
+    pub use super::v1::*;
+
+    #[unstable(feature = "prelude_2021", issue = "none")]
+    #[doc(no_inline)]
+    pub use crate::iter::FromIterator;
+
+    #[unstable(feature = "prelude_2021", issue = "none")]
+    #[doc(no_inline)]
+    pub use crate::convert::{TryFrom, TryInto};
+}
@@ -195,6 +195,8 @@
     test(no_crate_inject, attr(deny(warnings))),
     test(attr(allow(dead_code, deprecated, unused_variables, unused_mut)))
 )]
+// `std::os` and the platform extension modules are annotated with `doc(cfg)` by hand.
+#![doc(auto_cfg = false)]
 // Don't link to std. We are std.
 #![no_std]
 #![warn(deprecated_in_future)]
@@ -85,3 +85,22 @@
 #![stable(feature = "rust1", since = "1.0.0")]
 
 pub mod v1;
+
+/// The 2021 edition of the prelude of The Rust Standard Library.
+///
+/// This is the `v1` prelude together with the conversion and collection
+/// traits that only become part of the prelude in the 2021 edition.
+#[unstable(feature = "prelude_2021", issue = "none")]
+pub mod rust_2021 {
+    #[unstable(feature = "prelude_2021", issue = "none")]
+    #[doc(no_inline)]
+    pub use super::v1::*;
+
+    #[unstable(feature = "prelude_2021", issue = "none")]
+    #[doc(no_inline)]
+    pub use core::iter::FromIterator;
+
+    #[unstable(feature = "prelude_2021", issue = "none")]
+    #[doc(no_inline)]
+    pub use core::convert::{TryFrom, TryInto};
+}
@@ -236,3 +236,11 @@ fn test_mutex_unsized() {
     let comp: &[i32] = &[4, 2, 5];
     assert_eq!(&*mutex.lock().unwrap(), comp);
 }
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_mutex_is_unboxed() {
+    // The futex-based mutex is a single atomic word stored inline, next to the
+    // poison flag.
+    assert_eq!(crate::mem::size_of::<Mutex<()>>(), 8);
+}
@@ -245,3 +245,11 @@ fn test_get_mut_poison() {
         Ok(x) => panic!("get_mut of poisoned RwLock is Ok: {:?}", x),
     }
 }
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_rwlock_is_unboxed() {
+    // The futex-based rwlock is two atomic words stored inline, next to the
+    // poison flag.
+    assert_eq!(crate::mem::size_of::<RwLock<()>>(), 12);
+}
@@ -3,9 +3,14 @@
 use crate::convert::TryInto;
 use crate::ptr::null;
 use crate::sync::atomic::AtomicI32;
+use crate::sys::os::errno;
 use crate::time::Duration;
 
-pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) {
+/// Waits for a `futex_wake` on `futex`, as long as it still contains `expected`.
+///
+/// Returns false if the wait timed out, and true otherwise. Like all waits, this
+/// may also return spuriously.
+pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) -> bool {
     let timespec = timeout.and_then(|d| {
         Some(libc::timespec {
             // Sleep forever if the timeout is longer than fits in a timespec.
@@ -14,24 +19,41 @@ pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) {
             tv_nsec: d.subsec_nanos() as _,
         })
     });
-    unsafe {
+    let r = unsafe {
         libc::syscall(
             libc::SYS_futex,
             futex as *const AtomicI32,
             libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
             expected,
             timespec.as_ref().map_or(null(), |d| d as *const libc::timespec),
-        );
-    }
+        )
+    };
+    !(r < 0 && errno() == libc::ETIMEDOUT)
 }
 
-pub fn futex_wake(futex: &AtomicI32) {
+/// Wakes up one thread that's blocked on `futex_wait` on this futex.
+///
+/// Returns true if this actually woke up such a thread,
+/// or false if no thread was waiting on this futex.
+pub fn futex_wake(futex: &AtomicI32) -> bool {
     unsafe {
         libc::syscall(
             libc::SYS_futex,
             futex as *const AtomicI32,
             libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
             1,
+        ) > 0
+    }
+}
+
+/// Wakes up all threads that are waiting on `futex_wait` on this futex.
+pub fn futex_wake_all(futex: &AtomicI32) {
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            futex as *const AtomicI32,
+            libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+            i32::MAX,
         );
     }
 }
@@ -44,7 +44,6 @@ pub mod alloc;
 pub mod android;
 pub mod args;
 pub mod cmath;
-pub mod condvar;
 pub mod env;
 pub mod ext;
 pub mod fd;
@@ -54,7 +53,6 @@ pub mod io;
 #[cfg(target_os = "l4re")]
 mod l4re;
 pub mod memchr;
-pub mod mutex;
 #[cfg(not(target_os = "l4re"))]
 pub mod net;
 #[cfg(target_os = "l4re")]
@@ -64,7 +62,7 @@ pub mod path;
 pub mod pipe;
 pub mod process;
 pub mod rand;
-pub mod rwlock;
+mod remutex;
 pub mod stack_overflow;
 pub mod stdio;
 pub mod thread;
@@ -72,6 +70,21 @@ pub mod thread_local_dtor;
 pub mod thread_local_key;
 pub mod time;
 
+cfg_if::cfg_if! {
+    if #[cfg(any(target_os = "linux", target_os = "android"))] {
+        #[path = "condvar_futex.rs"]
+        pub mod condvar;
+        #[path = "mutex_futex.rs"]
+        pub mod mutex;
+        #[path = "rwlock_futex.rs"]
+        pub mod rwlock;
+    } else {
+        pub mod condvar;
+        pub mod mutex;
+        pub mod rwlock;
+    }
+}
+
 pub use crate::sys_common::os_str_bytes as os_str;
 
 #[cfg(not(test))]
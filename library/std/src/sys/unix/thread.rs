@@ -2,6 +2,7 @@ use crate::cmp;
 use crate::ffi::CStr;
 use crate::io;
 use crate::mem;
+use crate::num::NonZeroUsize;
 use crate::ptr;
 use crate::sys::{os, stack_overflow};
 use crate::time::Duration;
@@ -212,6 +213,174 @@ impl Drop for Thread {
     }
 }
 
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    cfg_if::cfg_if! {
+        if #[cfg(any(target_os = "android", target_os = "linux"))] {
+            // The affinity mask restricts the CPUs we may run on, e.g. when started with
+            // `taskset`, and is more accurate than the number of online CPUs. Container runtimes
+            // usually limit CPU time through a cgroup quota instead, which is accounted for too.
+            let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+            let cpus = unsafe {
+                if libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) == 0 {
+                    (0..libc::CPU_SETSIZE as usize).filter(|&i| libc::CPU_ISSET(i, &set)).count()
+                } else {
+                    match libc::sysconf(libc::_SC_NPROCESSORS_ONLN) {
+                        -1 => return Err(io::Error::last_os_error()),
+                        cpus => cpus as usize,
+                    }
+                }
+            };
+            NonZeroUsize::new(cmp::min(cpus, cgroups::quota())).ok_or_else(unknown_parallelism)
+        } else if #[cfg(any(
+            target_os = "emscripten",
+            target_os = "fuchsia",
+            target_os = "ios",
+            target_os = "macos",
+            target_os = "solaris",
+            target_os = "illumos",
+        ))] {
+            match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
+                -1 => Err(io::Error::last_os_error()),
+                cpus => NonZeroUsize::new(cpus as usize).ok_or_else(unknown_parallelism),
+            }
+        } else if #[cfg(any(
+            target_os = "freebsd",
+            target_os = "dragonfly",
+            target_os = "netbsd",
+        ))] {
+            let mut cpus: libc::c_uint = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) as _ };
+            if cpus < 1 {
+                let mut mib = [libc::CTL_HW, libc::HW_NCPU, 0, 0];
+                let mut cpus_size = mem::size_of_val(&cpus);
+                let res = unsafe {
+                    libc::sysctl(
+                        mib.as_mut_ptr(),
+                        2,
+                        &mut cpus as *mut _ as *mut _,
+                        &mut cpus_size as *mut _ as *mut _,
+                        ptr::null_mut(),
+                        0,
+                    )
+                };
+                if res == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            NonZeroUsize::new(cpus as usize).ok_or_else(unknown_parallelism)
+        } else if #[cfg(target_os = "openbsd")] {
+            let mut cpus: libc::c_uint = 0;
+            let mut cpus_size = mem::size_of_val(&cpus);
+            let mut mib = [libc::CTL_HW, libc::HW_NCPU, 0, 0];
+            let res = unsafe {
+                libc::sysctl(
+                    mib.as_mut_ptr(),
+                    2,
+                    &mut cpus as *mut _ as *mut _,
+                    &mut cpus_size as *mut _ as *mut _,
+                    ptr::null_mut(),
+                    0,
+                )
+            };
+            if res == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            NonZeroUsize::new(cpus as usize).ok_or_else(unknown_parallelism)
+        } else {
+            // FIXME: implement on vxWorks, Redox, Haiku, l4re
+            Err(unknown_parallelism())
+        }
+    }
+}
+
+fn unknown_parallelism() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        "The number of hardware threads is not known for the target platform",
+    )
+}
+
+/// CPU bandwidth limits of Linux control groups.
+///
+/// A process whose cgroup, or one of its ancestors, has a CPU quota only gets `quota / period`
+/// CPUs worth of time no matter how many CPUs it may run on, so running more threads than that
+/// just makes them wait for each other.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod cgroups {
+    use crate::fs;
+    use crate::path::Path;
+
+    /// Returns the number of CPUs the cgroups of the current process allow it to use, which is
+    /// at least 1, or `usize::MAX` if none of them has a quota.
+    pub fn quota() -> usize {
+        let cgroups = match fs::read_to_string("/proc/self/cgroup") {
+            Ok(cgroups) => cgroups,
+            Err(_) => return usize::MAX,
+        };
+
+        let mut quota = usize::MAX;
+        for line in cgroups.lines() {
+            // Each line is `hierarchy-ID:controller-list:cgroup-path`.
+            let mut parts = line.splitn(3, ':');
+            let (id, controllers, path) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(id), Some(controllers), Some(path)) => (id, controllers, path),
+                _ => continue,
+            };
+            if id == "0" && controllers.is_empty() {
+                // The cgroup v2 unified hierarchy.
+                quota = quota.min(min_quota(Path::new("/sys/fs/cgroup"), path, quota_v2));
+            } else if controllers.split(',').any(|c| c == "cpu") {
+                // The cgroup v1 cpu controller hierarchy, usually mounted together with the
+                // cpuacct controller and linked to from here.
+                quota = quota.min(min_quota(Path::new("/sys/fs/cgroup/cpu"), path, quota_v1));
+            }
+        }
+        quota
+    }
+
+    /// Reads the quota of the cgroup at `path` in the hierarchy mounted at `mount` and of all of
+    /// its ancestors, returning the smallest one.
+    ///
+    /// Inside a cgroup namespace `path` is relative to the namespace root, which is also what is
+    /// mounted, so the quota of the mount point itself is always taken into account.
+    fn min_quota(mount: &Path, path: &str, read: fn(&Path) -> Option<usize>) -> usize {
+        let mut dir = mount.join(path.trim_start_matches('/'));
+        let mut quota = usize::MAX;
+        loop {
+            if let Some(q) = read(&dir) {
+                quota = quota.min(q);
+            }
+            if dir == mount || !dir.pop() {
+                break;
+            }
+        }
+        quota
+    }
+
+    /// Reads `cpu.max`, which contains `max` instead of a number when there is no quota.
+    fn quota_v2(dir: &Path) -> Option<usize> {
+        let max = fs::read_to_string(dir.join("cpu.max")).ok()?;
+        let mut parts = max.split_whitespace();
+        let quota = parts.next()?.parse().ok()?;
+        let period = parts.next()?.parse().ok()?;
+        cpus(quota, period)
+    }
+
+    /// Reads `cpu.cfs_quota_us`, which is -1 when there is no quota, and `cpu.cfs_period_us`.
+    fn quota_v1(dir: &Path) -> Option<usize> {
+        let quota = fs::read_to_string(dir.join("cpu.cfs_quota_us")).ok()?.trim().parse().ok()?;
+        let period = fs::read_to_string(dir.join("cpu.cfs_period_us")).ok()?.trim().parse().ok()?;
+        cpus(quota, period)
+    }
+
+    fn cpus(quota: u64, period: u64) -> Option<usize> {
+        if period == 0 {
+            return None;
+        }
+        // A quota of less than a full period still lets one thread make progress.
+        Some(((quota / period) as usize).max(1))
+    }
+}
+
 #[cfg(all(
     not(target_os = "linux"),
     not(target_os = "freebsd"),
@@ -1,7 +1,9 @@
-use super::Builder;
+use super::{Builder, Scope};
 use crate::any::Any;
 use crate::mem;
+use crate::panic;
 use crate::result;
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use crate::sync::mpsc::{channel, Sender};
 use crate::thread::{self, ThreadId};
 use crate::time::Duration;
@@ -258,5 +260,80 @@ fn test_thread_id_not_equal() {
     assert!(thread::current().id() != spawned_id);
 }
 
+#[test]
+fn test_available_parallelism() {
+    // Every platform the test suite runs on knows how many CPUs it has.
+    assert!(thread::available_parallelism().unwrap().get() >= 1);
+}
+
+#[test]
+fn test_scoped_threads_borrow() {
+    let mut a = vec![1, 2, 3];
+    let mut x = 0;
+    thread::scope(|s| {
+        s.spawn(|| assert_eq!(a.len(), 3));
+        s.spawn(|| x += a[0] + a[2]);
+    });
+    a.push(4);
+    assert_eq!(x, a.len());
+}
+
+#[test]
+fn test_scoped_threads_nested() {
+    let counter = &AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(move || {
+                s.spawn(move || counter.fetch_add(1, Ordering::Relaxed));
+                counter.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+    });
+    assert_eq!(counter.load(Ordering::Relaxed), 8);
+}
+
+#[test]
+fn test_scoped_threads_join() {
+    let result = thread::scope(|s| {
+        let t = s.spawn(|| panic!("oh no"));
+        assert!(t.join().is_err());
+        s.spawn(|| 42).join().unwrap()
+    });
+    assert_eq!(result, 42);
+}
+
+#[test]
+fn test_scoped_threads_unjoined_panic() {
+    let result = panic::catch_unwind(|| {
+        thread::scope(|s| {
+            s.spawn(|| panic!("oh no"));
+        })
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_scoped_threads_drop_result_before_join() {
+    let actually_finished = &AtomicBool::new(false);
+    struct X<'scope, 'env>(&'scope Scope<'scope, 'env>, &'env AtomicBool);
+    impl Drop for X<'_, '_> {
+        fn drop(&mut self) {
+            thread::sleep(Duration::from_millis(20));
+            let actually_finished = self.1;
+            self.0.spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                actually_finished.store(true, Ordering::Relaxed);
+            });
+        }
+    }
+    thread::scope(|s| {
+        s.spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            X(s, actually_finished)
+        });
+    });
+    assert!(actually_finished.load(Ordering::Relaxed));
+}
+
 // NOTE: the corresponding test for stderr is in ui/thread-stderr, due
 // to the test harness apparently interfering with stderr configuration.
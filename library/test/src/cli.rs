@@ -2,6 +2,7 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use super::helpers::isatty;
 use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
@@ -24,6 +25,9 @@ pub struct TestOpts {
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
     pub time_options: Option<TestTimeOptions>,
+    pub test_timeout: Option<Duration>,
+    pub shuffle: bool,
+    pub shuffle_seed: Option<u64>,
     pub options: Options,
 }
 
@@ -101,8 +105,9 @@ fn optgroups() -> getopts::Options {
             "Configure formatting of output:
             pretty = Print verbose output;
             terse  = Display one character per test;
-            json   = Output a json document",
-            "pretty|terse|json",
+            json   = Output a json document;
+            junit  = Output a JUnit document",
+            "pretty|terse|json|junit",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -143,6 +148,25 @@ fn optgroups() -> getopts::Options {
 
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
+        )
+        .optopt(
+            "",
+            "test-timeout",
+            "Fail tests that are still running after SECS seconds.
+
+            Tests run in a separate process (with panic=abort) are killed.
+            Tests run in-process can't be stopped, they are reported as failed
+            and the test binary exits once the other tests are done.
+
+            Individual tests can override the limit with `#[test_timeout(SECS)]`.",
+            "SECS",
+        )
+        .optflag("", "shuffle", "Run tests in random order")
+        .optopt(
+            "",
+            "shuffle-seed",
+            "Run tests in random order; seed the random number generator with SEED",
+            "SEED",
         );
     opts
 }
@@ -159,6 +183,12 @@ By default, all tests are run in parallel. This can be altered with the
 --test-threads flag or the RUST_TEST_THREADS environment variable when running
 tests (set it to 1).
 
+By default, the tests are run in alphabetical order. Use --shuffle or set
+RUST_TEST_SHUFFLE to run the tests in random order. Pass the generated
+"shuffle seed" to --shuffle-seed (or set RUST_TEST_SHUFFLE_SEED) to run the
+tests in the same order again. Note that --shuffle and --shuffle-seed do not
+affect whether the tests are run in parallel.
+
 All tests have their standard output and standard error captured by default.
 This can be overridden with the --nocapture flag or setting RUST_TEST_NOCAPTURE
 environment variable to a value other than "0". Logging is not captured by default.
@@ -176,7 +206,10 @@ Test Attributes:
     `#[ignore]`       - When applied to a function which is already attributed as a
                         test, then the test runner will ignore these tests during
                         normal test runs. Running with --ignored or --include-ignored will run
-                        these tests."#,
+                        these tests.
+    `#[test_timeout(SECS)]` - Fails the test if it runs for longer than SECS
+                        seconds, overriding --test-timeout. Requires
+                        `#![feature(test_timeout)]`."#,
         usage = options.usage(&message)
     );
 }
@@ -248,6 +281,9 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let test_threads = get_test_threads(&matches)?;
     let color = get_color_config(&matches)?;
     let format = get_format(&matches, quiet, allow_unstable)?;
+    let shuffle = get_shuffle(&matches, allow_unstable)?;
+    let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
 
     let options = Options::new().display_output(matches.opt_present("show-output"));
 
@@ -267,6 +303,9 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         test_threads,
         skip,
         time_options,
+        test_timeout,
+        shuffle,
+        shuffle_seed,
         options,
     };
 
@@ -307,6 +346,83 @@ fn get_time_options(
     Ok(options)
 }
 
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let test_timeout = match matches.opt_str("test-timeout") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(_) if !allow_unstable => {
+                return Err("The \"test-timeout\" flag is only accepted on the nightly compiler \
+                            with -Z unstable-options"
+                    .into());
+            }
+            Ok(0) => return Err("argument for --test-timeout must not be 0".into()),
+            Ok(n) => Some(Duration::from_secs(n)),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --test-timeout must be a number \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => None,
+    };
+
+    Ok(test_timeout)
+}
+
+fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
+    let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
+    if !shuffle && allow_unstable {
+        shuffle = match env::var("RUST_TEST_SHUFFLE") {
+            Ok(val) => &val != "0",
+            Err(_) => false,
+        };
+    }
+
+    Ok(shuffle)
+}
+
+fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<u64>> {
+    let mut shuffle_seed = match matches.opt_str("shuffle-seed") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(_) if !allow_unstable => {
+                return Err("The \"shuffle-seed\" flag is only accepted on the nightly compiler \
+                            with -Z unstable-options"
+                    .into());
+            }
+            Ok(n) => Some(n),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --shuffle-seed must be a number \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => None,
+    };
+
+    if shuffle_seed.is_none() && allow_unstable {
+        shuffle_seed = match env::var("RUST_TEST_SHUFFLE_SEED") {
+            Ok(val) => match val.parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    return Err(format!(
+                        "RUST_TEST_SHUFFLE_SEED is `{}`, should be a number.",
+                        val
+                    ));
+                }
+            },
+            Err(_) => None,
+        };
+    }
+
+    Ok(shuffle_seed)
+}
+
 fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
@@ -341,10 +457,16 @@ fn get_format(
             }
             OutputFormat::Json
         }
+        Some("junit") => {
+            if !allow_unstable {
+                return Err("The \"junit\" format is only accepted on the nightly compiler".into());
+            }
+            OutputFormat::Junit
+        }
 
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, or json (was \
+                "argument for --format must be pretty, terse, json or junit (was \
                  {})",
                 v
             ));
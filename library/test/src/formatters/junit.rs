@@ -0,0 +1,196 @@
+use std::{fmt, io, io::prelude::Write, mem, time::Duration};
+
+use super::OutputFormatter;
+use crate::{
+    console::{ConsoleTestState, OutputLocation},
+    test_result::TestResult,
+    time,
+    types::{TestDesc, TestType},
+};
+
+/// Formatter producing a JUnit XML report. JUnit reports describe the whole run at once, so the
+/// results are collected as the tests complete and the document is written out at the end.
+pub(crate) struct JunitFormatter<T> {
+    out: OutputLocation<T>,
+    shuffle_seed: Option<u64>,
+    results: Vec<(TestDesc, TestResult, Duration, Vec<u8>)>,
+}
+
+impl<T: Write> JunitFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out, shuffle_seed: None, results: Vec::new() }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn write_message(&mut self, s: &str) -> io::Result<()> {
+        assert!(!s.contains('\n'));
+
+        self.out.write_all(s.as_ref())
+    }
+
+    fn write_testcase(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        duration: Duration,
+        stdout: &[u8],
+    ) -> io::Result<()> {
+        let (class_name, test_name) = parse_class_name(desc);
+        self.write_message(&*format!(
+            r#"<testcase classname="{}" name="{}" time="{}">"#,
+            EscapedString(&class_name),
+            EscapedString(&test_name),
+            duration.as_secs_f64()
+        ))?;
+        match *result {
+            TestResult::TrOk | TestResult::TrAllowedFail | TestResult::TrBench(_) => {}
+            TestResult::TrIgnored => self.write_message("<skipped/>")?,
+            TestResult::TrFailed => self.write_message(r#"<failure type="assert"/>"#)?,
+            TestResult::TrFailedMsg(ref m) => self.write_message(&*format!(
+                r#"<failure type="assert" message="{}"/>"#,
+                EscapedString(m)
+            ))?,
+            TestResult::TrTimedFail => {
+                self.write_message(r#"<failure type="timeout" message="time limit exceeded"/>"#)?
+            }
+        }
+        if !stdout.is_empty() {
+            self.write_message(&*format!(
+                "<system-out>{}</system-out>",
+                EscapedString(String::from_utf8_lossy(stdout))
+            ))?;
+        }
+        self.write_message("</testcase>")
+    }
+}
+
+impl<T: Write> OutputFormatter for JunitFormatter<T> {
+    fn write_run_start(&mut self, _test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.shuffle_seed = shuffle_seed;
+        Ok(())
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // We do not output anything on test start.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // A test running for too long is only a warning, the report is about results.
+        Ok(())
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        // Like the other formatters, only show the output of passing tests when asked to.
+        let display_stdout = state.options.display_output || *result != TestResult::TrOk;
+        let stdout = if display_stdout { stdout.to_vec() } else { Vec::new() };
+        let duration = exec_time.map(|t| t.0).unwrap_or_default();
+        self.results.push((desc.clone(), result.clone(), duration, stdout));
+        Ok(())
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let total_time: Duration = self.results.iter().map(|(_, _, duration, _)| *duration).sum();
+
+        self.write_message(r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        self.write_message("<testsuites>")?;
+        self.write_message(&*format!(
+            "<testsuite name=\"test\" package=\"test\" id=\"0\" \
+             errors=\"0\" \
+             failures=\"{}\" \
+             tests=\"{}\" \
+             skipped=\"{}\" \
+             time=\"{}\">",
+            state.failed,
+            state.total,
+            state.ignored,
+            total_time.as_secs_f64()
+        ))?;
+        if let Some(shuffle_seed) = self.shuffle_seed {
+            self.write_message(&*format!(
+                r#"<properties><property name="shuffle_seed" value="{}"/></properties>"#,
+                shuffle_seed
+            ))?;
+        }
+        for (desc, result, duration, stdout) in mem::take(&mut self.results) {
+            self.write_testcase(&desc, &result, duration, &stdout)?;
+        }
+        self.write_message("</testsuite>")?;
+        self.write_message("</testsuites>")?;
+        self.out.write_all(b"\n")?;
+
+        Ok(state.failed == 0)
+    }
+}
+
+/// Splits the test name into the `classname` and `name` attributes of a JUnit test case.
+fn parse_class_name(desc: &TestDesc) -> (String, String) {
+    let name = desc.name.as_slice().trim_end();
+    match desc.test_type {
+        // Doctest names look like `src/lib.rs - module::item (line 42)`.
+        TestType::DocTest => match name.splitn(2, " - ").collect::<Vec<_>>()[..] {
+            [file, item] => (String::from(file), String::from(item)),
+            _ => (String::from("doctest"), String::from(name)),
+        },
+        TestType::IntegrationTest => (String::from("integration"), String::from(name)),
+        // Unit tests are named after their module path, which becomes the class.
+        TestType::UnitTest | TestType::Unknown => match name.rfind("::") {
+            Some(idx) => (String::from(&name[..idx]), String::from(&name[idx + 2..])),
+            None => (String::from("crate"), String::from(name)),
+        },
+    }
+}
+
+/// A formatting utility used to print strings with characters in need of escaping in XML text
+/// and attribute values. Control characters aren't allowed in XML 1.0 documents at all and are
+/// dropped.
+struct EscapedString<S: AsRef<str>>(S);
+
+impl<S: AsRef<str>> fmt::Display for EscapedString<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.0.as_ref();
+        let mut start = 0;
+
+        for (i, byte) in s.bytes().enumerate() {
+            let escaped = match byte {
+                b'&' => "&amp;",
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'"' => "&quot;",
+                b'\'' => "&apos;",
+                b'\n' => "&#10;",
+                b'\r' => "&#13;",
+                b'\t' => "&#9;",
+                b'\x00'..=b'\x1f' => "",
+                _ => {
+                    continue;
+                }
+            };
+
+            if start < i {
+                f.write_str(&s[start..i])?;
+            }
+
+            f.write_str(escaped)?;
+
+            start = i + 1;
+        }
+
+        if start != s.len() {
+            f.write_str(&s[start..])?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+//! Helper module which shuffles the order in which tests are run, using a
+//! seed so that a given order can be reproduced.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cli::TestOpts;
+use crate::types::{TestDescAndFn, TestName};
+
+/// Returns the seed the tests should be shuffled with, if any: the one given
+/// explicitly through `--shuffle-seed`, or a fresh one derived from the
+/// current time when only `--shuffle` was passed.
+pub fn get_shuffle_seed(opts: &TestOpts) -> Option<u64> {
+    opts.shuffle_seed.or_else(|| {
+        if opts.shuffle {
+            Some(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Failed to get system time")
+                    .as_nanos() as u64,
+            )
+        } else {
+            None
+        }
+    })
+}
+
+/// Shuffles `tests` in place. The resulting order only depends on the seed and
+/// on the set of test names, so re-running the same binary with the same
+/// filters and seed reproduces it exactly.
+pub fn shuffle_tests(shuffle_seed: u64, tests: &mut [TestDescAndFn]) {
+    let test_names: Vec<&TestName> = tests.iter().map(|test| &test.desc.name).collect();
+    let test_names_hash = calculate_hash(&test_names);
+    let mut rng = Rng::new(shuffle_seed, test_names_hash);
+    shuffle(&mut rng, tests);
+}
+
+// Fisher-Yates shuffle.
+fn shuffle<T>(rng: &mut Rng, slice: &mut [T]) {
+    for i in 0..slice.len() {
+        randomize_first(rng, &mut slice[i..]);
+    }
+
+    fn randomize_first<T>(rng: &mut Rng, slice: &mut [T]) {
+        assert!(!slice.is_empty());
+        let idx = rng.rand_range(0..slice.len() as u64) as usize;
+        slice.swap(0, idx);
+    }
+}
+
+/// A tiny deterministic pseudo-random number generator. `DefaultHasher` is
+/// keyed with fixed keys, so its output is stable for a given input.
+struct Rng {
+    state: u64,
+    extra: u64,
+}
+
+impl Rng {
+    fn new(seed: u64, extra: u64) -> Self {
+        Self { state: seed, extra }
+    }
+
+    fn rand_range(&mut self, range: std::ops::Range<u64>) -> u64 {
+        self.rand_u64() % (range.end - range.start) + range.start
+    }
+
+    fn rand_u64(&mut self) -> u64 {
+        self.state = calculate_hash(&(self.state, self.extra));
+        self.state
+    }
+}
+
+fn calculate_hash<T: Hash>(t: &T) -> u64 {
+    let mut s = DefaultHasher::new();
+    t.hash(&mut s);
+    s.finish()
+}
@@ -23,6 +23,7 @@
 #![cfg_attr(any(unix, target_os = "cloudabi"), feature(libc))]
 #![feature(rustc_private)]
 #![feature(nll)]
+#![feature(available_parallelism)]
 #![feature(bool_to_option)]
 #![feature(set_stdio)]
 #![feature(panic_unwind)]
@@ -61,7 +62,7 @@ pub mod test {
 
 use std::{
     env, io,
-    io::prelude::Write,
+    io::prelude::{Read, Write},
     panic::{self, catch_unwind, AssertUnwindSafe, PanicInfo},
     process::{self, Command, Termination},
     sync::mpsc::{channel, Sender},
@@ -88,6 +89,7 @@ mod tests;
 use event::{CompletedTest, TestEvent};
 use helpers::concurrency::get_concurrency;
 use helpers::exit_code::get_exit_code;
+use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use helpers::sink::Sink;
 use options::{Concurrent, RunStrategy};
 use test_result::*;
@@ -205,7 +207,7 @@ pub fn run_tests<F>(
 where
     F: FnMut(TestEvent) -> io::Result<()>,
 {
-    use std::collections::{self, HashMap};
+    use std::collections::{self, HashMap, HashSet};
     use std::hash::BuildHasherDefault;
     use std::sync::mpsc::RecvTimeoutError;
     // Use a deterministic hasher
@@ -228,13 +230,23 @@ where
         filtered_tests
     };
 
+    let shuffle_seed = get_shuffle_seed(opts);
+    let filtered_tests = {
+        let mut filtered_tests = filtered_tests;
+        if let Some(shuffle_seed) = shuffle_seed {
+            shuffle_tests(shuffle_seed, &mut filtered_tests);
+        }
+
+        filtered_tests
+    };
+
     let filtered_out = tests_len - filtered_tests.len();
     let event = TestEvent::TeFilteredOut(filtered_out);
     notify_about_test_event(event)?;
 
     let filtered_descs = filtered_tests.iter().map(|t| t.desc.clone()).collect();
 
-    let event = TestEvent::TeFiltered(filtered_descs);
+    let event = TestEvent::TeFiltered(filtered_descs, shuffle_seed);
     notify_about_test_event(event)?;
 
     let (filtered_tests, filtered_benchs): (Vec<_>, _) = filtered_tests
@@ -256,6 +268,14 @@ where
 
     let mut running_tests: TestMap = HashMap::default();
 
+    // Tests run in-process can't be stopped once they exceed their hard time limit, so the
+    // deadlines are enforced here: hung tests are reported as failed and their threads are left
+    // behind. Tests run in a subprocess are killed by `spawn_test_subprocess` instead.
+    let enforce_deadlines = matches!(run_strategy, RunStrategy::InProcess)
+        && remaining.iter().any(|t| time::get_test_timeout(&t.desc, opts.test_timeout).is_some());
+    let mut deadlines: TestMap = HashMap::default();
+    let mut hung_tests: HashSet<TestDesc> = HashSet::new();
+
     fn get_timed_out_tests(running_tests: &mut TestMap) -> Vec<TestDesc> {
         let now = Instant::now();
         let timed_out = running_tests
@@ -268,14 +288,24 @@ where
         timed_out
     };
 
-    fn calc_timeout(running_tests: &TestMap) -> Option<Duration> {
-        running_tests.values().min().map(|next_timeout| {
+    fn pop_hung_test(deadlines: &mut TestMap) -> Option<TestDesc> {
+        let now = Instant::now();
+        let hung_test = deadlines
+            .iter()
+            .find(|(_, deadline)| &now >= *deadline)
+            .map(|(desc, _)| desc.clone())?;
+        deadlines.remove(&hung_test);
+        Some(hung_test)
+    }
+
+    fn calc_timeout(running_tests: &TestMap, deadlines: &TestMap) -> Option<Duration> {
+        running_tests.values().chain(deadlines.values()).min().map(|next_timeout| {
             let now = Instant::now();
             if *next_timeout >= now { *next_timeout - now } else { Duration::new(0, 0) }
         })
     };
 
-    if concurrency == 1 {
+    if concurrency == 1 && !enforce_deadlines {
         while !remaining.is_empty() {
             let test = remaining.pop().unwrap();
             let event = TestEvent::TeWait(test.desc.clone());
@@ -287,11 +317,16 @@ where
             notify_about_test_event(event)?;
         }
     } else {
-        while pending > 0 || !remaining.is_empty() {
+        'running: while pending > 0 || !remaining.is_empty() {
             while pending < concurrency && !remaining.is_empty() {
                 let test = remaining.pop().unwrap();
                 let timeout = time::get_default_test_timeout();
                 running_tests.insert(test.desc.clone(), timeout);
+                if enforce_deadlines {
+                    if let Some(limit) = time::get_test_timeout(&test.desc, opts.test_timeout) {
+                        deadlines.insert(test.desc.clone(), Instant::now() + limit);
+                    }
+                }
 
                 let event = TestEvent::TeWait(test.desc.clone());
                 notify_about_test_event(event)?; //here no pad
@@ -301,13 +336,27 @@ where
 
             let mut res;
             loop {
-                if let Some(timeout) = calc_timeout(&running_tests) {
+                if let Some(timeout) = calc_timeout(&running_tests, &deadlines) {
                     res = rx.recv_timeout(timeout);
                     for test in get_timed_out_tests(&mut running_tests) {
                         let event = TestEvent::TeTimeout(test);
                         notify_about_test_event(event)?;
                     }
 
+                    if let Err(RecvTimeoutError::Timeout) = res {
+                        if let Some(test) = pop_hung_test(&mut deadlines) {
+                            running_tests.remove(&test);
+                            hung_tests.insert(test.clone());
+
+                            let result = TrFailedMsg("timed out".to_string());
+                            let completed_test = CompletedTest::new(test, result, None, Vec::new());
+                            let event = TestEvent::TeResult(completed_test);
+                            notify_about_test_event(event)?;
+                            pending -= 1;
+                            continue 'running;
+                        }
+                    }
+
                     match res {
                         Err(RecvTimeoutError::Timeout) => {
                             // Result is not yet ready, continue waiting.
@@ -324,7 +373,12 @@ where
             }
 
             let completed_test = res.unwrap();
+            if hung_tests.contains(&completed_test.desc) {
+                // The test finished after all, but it has been reported as timed out already.
+                continue;
+            }
             running_tests.remove(&completed_test.desc);
+            deadlines.remove(&completed_test.desc);
 
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
@@ -433,7 +487,9 @@ pub fn run_test(
         pub strategy: RunStrategy,
         pub nocapture: bool,
         pub concurrency: Concurrent,
+        pub report_time: bool,
         pub time: Option<time::TestTimeOptions>,
+        pub timeout: Option<Duration>,
     }
 
     fn run_test_inner(
@@ -449,7 +505,7 @@ pub fn run_test(
             RunStrategy::InProcess => run_test_in_process(
                 desc,
                 opts.nocapture,
-                opts.time.is_some(),
+                opts.report_time,
                 testfn,
                 monitor_ch,
                 opts.time,
@@ -457,9 +513,10 @@ pub fn run_test(
             RunStrategy::SpawnPrimary => spawn_test_subprocess(
                 desc,
                 opts.nocapture,
-                opts.time.is_some(),
+                opts.report_time,
                 monitor_ch,
                 opts.time,
+                opts.timeout,
             ),
         };
 
@@ -475,8 +532,16 @@ pub fn run_test(
         }
     }
 
-    let test_run_opts =
-        TestRunOpts { strategy, nocapture: opts.nocapture, concurrency, time: opts.time_options };
+    // JUnit reports always carry the duration of each test case.
+    let report_time = opts.time_options.is_some() || opts.format == OutputFormat::Junit;
+    let test_run_opts = TestRunOpts {
+        strategy,
+        nocapture: opts.nocapture,
+        concurrency,
+        report_time,
+        time: opts.time_options,
+        timeout: time::get_test_timeout(&desc, opts.test_timeout),
+    };
 
     match testfn {
         DynBenchFn(bencher) => {
@@ -566,6 +631,7 @@ fn spawn_test_subprocess(
     report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    timeout: Option<Duration>,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
@@ -579,17 +645,22 @@ fn spawn_test_subprocess(
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
-            Ok(out) => out,
-            Err(e) => {
-                let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
-                return (TrFailed, err.into_bytes(), None);
-            }
+        let output = match timeout {
+            Some(timeout) => output_with_timeout(&mut command, nocapture, timeout),
+            None => command.output().map(Some),
         };
         let exec_time = start.map(|start| {
             let duration = start.elapsed();
             TestExecTime(duration)
         });
+        let output = match output {
+            Ok(Some(out)) => out,
+            Ok(None) => return (TrFailedMsg("timed out".to_string()), Vec::new(), exec_time),
+            Err(e) => {
+                let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
+                return (TrFailed, err.into_bytes(), None);
+            }
+        };
 
         let std::process::Output { stdout, stderr, status } = output;
         let mut test_output = stdout;
@@ -614,6 +685,55 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
+/// Like `Command::output`, but kills the child and returns `Ok(None)` if it is still running
+/// after `timeout`.
+fn output_with_timeout(
+    command: &mut Command,
+    nocapture: bool,
+    timeout: Duration,
+) -> io::Result<Option<process::Output>> {
+    if !nocapture {
+        command.stdout(process::Stdio::piped());
+        command.stderr(process::Stdio::piped());
+    }
+    let mut child = command.spawn()?;
+
+    // Drain the pipes while waiting, a child blocked on a full pipe would never exit.
+    fn read_to_end<R: Read + Send + 'static>(
+        pipe: Option<R>,
+    ) -> Option<thread::JoinHandle<io::Result<Vec<u8>>>> {
+        pipe.map(|mut pipe| {
+            thread::spawn(move || {
+                let mut buf = Vec::new();
+                pipe.read_to_end(&mut buf).map(|_| buf)
+            })
+        })
+    }
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            // The reader threads are left behind, in case the test leaked the pipes to
+            // processes of its own.
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let join = |reader: Option<thread::JoinHandle<io::Result<Vec<u8>>>>| match reader {
+        Some(reader) => reader.join().unwrap(),
+        None => Ok(Vec::new()),
+    };
+    Ok(Some(process::Output { status, stdout: join(stdout)?, stderr: join(stderr)? }))
+}
+
 fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Send>) -> ! {
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
@@ -3,7 +3,8 @@ use super::*;
 use crate::{
     bench::Bencher,
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    formatters::{JunitFormatter, OutputFormatter, PrettyFormatter},
+    helpers::shuffle::{get_shuffle_seed, shuffle_tests},
     options::OutputFormat,
     test::{
         filter_tests,
@@ -49,6 +50,9 @@ impl TestOpts {
             test_threads: None,
             skip: vec![],
             time_options: None,
+            test_timeout: None,
+            shuffle: false,
+            shuffle_seed: None,
             options: Options::new(),
         }
     }
@@ -62,6 +66,7 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 ignore: true,
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
+                timeout: None,
                 test_type: TestType::Unknown,
             },
             testfn: DynTestFn(Box::new(move || {})),
@@ -72,6 +77,7 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 ignore: false,
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
+                timeout: None,
                 test_type: TestType::Unknown,
             },
             testfn: DynTestFn(Box::new(move || {})),
@@ -90,6 +96,7 @@ pub fn do_not_run_ignored_tests() {
             ignore: true,
             should_panic: ShouldPanic::No,
             allow_fail: false,
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -109,6 +116,7 @@ pub fn ignored_tests_result_in_ignored() {
             ignore: true,
             should_panic: ShouldPanic::No,
             allow_fail: false,
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -132,6 +140,7 @@ fn test_should_panic() {
             ignore: false,
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -155,6 +164,7 @@ fn test_should_panic_good_message() {
             ignore: false,
             should_panic: ShouldPanic::YesWithMessage("error message"),
             allow_fail: false,
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -183,6 +193,7 @@ fn test_should_panic_bad_message() {
             ignore: false,
             should_panic: ShouldPanic::YesWithMessage(expected),
             allow_fail: false,
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -214,6 +225,7 @@ fn test_should_panic_non_string_message_type() {
             ignore: false,
             should_panic: ShouldPanic::YesWithMessage(expected),
             allow_fail: false,
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -235,6 +247,7 @@ fn test_should_panic_but_succeeds() {
             ignore: false,
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -253,6 +266,7 @@ fn report_time_test_template(report_time: bool) -> Option<TestExecTime> {
             ignore: false,
             should_panic: ShouldPanic::No,
             allow_fail: false,
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -286,6 +300,7 @@ fn time_test_failure_template(test_type: TestType) -> TestResult {
             ignore: false,
             should_panic: ShouldPanic::No,
             allow_fail: false,
+            timeout: None,
             test_type,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -323,6 +338,7 @@ fn typed_test_desc(test_type: TestType) -> TestDesc {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        timeout: None,
         test_type,
     }
 }
@@ -392,6 +408,94 @@ fn parse_include_ignored_flag() {
     assert_eq!(opts.run_ignored, RunIgnored::Yes);
 }
 
+#[test]
+fn parse_shuffle_flags() {
+    let args = vec!["progname".to_string(), "-Zunstable-options".to_string(), "--shuffle".into()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(opts.shuffle);
+    assert_eq!(opts.shuffle_seed, None);
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shuffle-seed".to_string(),
+        "123".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.shuffle_seed, Some(123));
+}
+
+#[test]
+fn parse_shuffle_flags_requires_unstable_options() {
+    let args = vec!["progname".to_string(), "--shuffle".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec!["progname".to_string(), "--shuffle-seed".to_string(), "123".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_test_timeout_option() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "30".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.test_timeout, Some(Duration::from_secs(30)));
+
+    let args = vec!["progname".to_string(), "--test-timeout".to_string(), "30".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn test_timeout_fails_hung_test() {
+    fn f() {}
+    // The hung test blocks until the sender is dropped, so that its thread exits once the
+    // tests are run.
+    let (tx, rx) = channel::<()>();
+
+    let desc = |name, timeout| TestDesc {
+        name: StaticTestName(name),
+        ignore: false,
+        should_panic: ShouldPanic::No,
+        allow_fail: false,
+        timeout,
+        test_type: TestType::Unknown,
+    };
+    let tests = vec![
+        // The attribute overrides the limit passed on the command line.
+        TestDescAndFn {
+            desc: desc("hangs", Some(1)),
+            testfn: DynTestFn(Box::new(move || {
+                let _ = rx.recv();
+            })),
+        },
+        TestDescAndFn { desc: desc("passes", None), testfn: DynTestFn(Box::new(f)) },
+    ];
+    let opts = TestOpts { test_timeout: Some(Duration::from_secs(3600)), ..TestOpts::new() };
+
+    let mut results = Vec::new();
+    run_tests(&opts, tests, |event| {
+        if let TestEvent::TeResult(completed_test) = event {
+            results.push((completed_test.desc.name.to_string(), completed_test.result));
+        }
+        Ok(())
+    })
+    .unwrap();
+    drop(tx);
+
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        results,
+        vec![
+            ("hangs".to_string(), TrFailedMsg("timed out".to_string())),
+            ("passes".to_string(), TrOk),
+        ]
+    );
+}
+
 #[test]
 pub fn filter_for_ignored_option() {
     // When we run ignored tests the test filter should filter out all the
@@ -439,6 +543,7 @@ pub fn exclude_should_panic_option() {
             ignore: false,
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(move || {})),
@@ -461,6 +566,7 @@ pub fn exact_filter_match() {
                     ignore: false,
                     should_panic: ShouldPanic::No,
                     allow_fail: false,
+                    timeout: None,
                     test_type: TestType::Unknown,
                 },
                 testfn: DynTestFn(Box::new(move || {})),
@@ -536,6 +642,7 @@ pub fn sort_tests() {
                     ignore: false,
                     should_panic: ShouldPanic::No,
                     allow_fail: false,
+                    timeout: None,
                     test_type: TestType::Unknown,
                 },
                 testfn: DynTestFn(Box::new(testfn)),
@@ -565,6 +672,75 @@ pub fn sort_tests() {
     }
 }
 
+fn sample_tests(count: usize) -> Vec<TestDescAndFn> {
+    (0..count)
+        .map(|i| TestDescAndFn {
+            desc: TestDesc {
+                name: DynTestName(format!("test::{}", i)),
+                ignore: false,
+                should_panic: ShouldPanic::No,
+                allow_fail: false,
+                timeout: None,
+                test_type: TestType::Unknown,
+            },
+            testfn: DynTestFn(Box::new(move || {})),
+        })
+        .collect()
+}
+
+fn test_names(tests: &[TestDescAndFn]) -> Vec<String> {
+    tests.iter().map(|test| test.desc.name.to_string()).collect()
+}
+
+#[test]
+pub fn shuffle_tests_is_reproducible() {
+    let mut left = sample_tests(100);
+    let mut right = sample_tests(100);
+
+    shuffle_tests(42, &mut left);
+    shuffle_tests(42, &mut right);
+
+    assert_eq!(test_names(&left), test_names(&right));
+}
+
+#[test]
+pub fn shuffle_tests_permutes_the_tests() {
+    let original = test_names(&sample_tests(100));
+    let mut tests = sample_tests(100);
+
+    shuffle_tests(42, &mut tests);
+    let shuffled = test_names(&tests);
+    assert_ne!(original, shuffled);
+
+    let mut sorted = shuffled;
+    sorted.sort();
+    let mut expected = original;
+    expected.sort();
+    assert_eq!(sorted, expected);
+}
+
+#[test]
+pub fn shuffle_tests_depends_on_the_seed() {
+    let mut left = sample_tests(100);
+    let mut right = sample_tests(100);
+
+    shuffle_tests(1, &mut left);
+    shuffle_tests(2, &mut right);
+
+    assert_ne!(test_names(&left), test_names(&right));
+}
+
+#[test]
+pub fn get_shuffle_seed_prefers_explicit_seed() {
+    let opts = TestOpts { shuffle: true, shuffle_seed: Some(7), ..TestOpts::new() };
+    assert_eq!(get_shuffle_seed(&opts), Some(7));
+
+    let opts = TestOpts { shuffle: true, ..TestOpts::new() };
+    assert!(get_shuffle_seed(&opts).is_some());
+
+    assert_eq!(get_shuffle_seed(&TestOpts::new()), None);
+}
+
 #[test]
 pub fn test_metricmap_compare() {
     let mut m1 = MetricMap::new();
@@ -613,6 +789,7 @@ pub fn test_bench_no_iter() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        timeout: None,
         test_type: TestType::Unknown,
     };
 
@@ -633,6 +810,7 @@ pub fn test_bench_iter() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        timeout: None,
         test_type: TestType::Unknown,
     };
 
@@ -647,6 +825,7 @@ fn should_sort_failures_before_printing_them() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        timeout: None,
         test_type: TestType::Unknown,
     };
 
@@ -655,6 +834,7 @@ fn should_sort_failures_before_printing_them() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        timeout: None,
         test_type: TestType::Unknown,
     };
 
@@ -686,3 +866,35 @@ fn should_sort_failures_before_printing_them() {
     let bpos = s.find("b").unwrap();
     assert!(apos < bpos);
 }
+
+#[test]
+fn junit_report_escapes_names_and_messages() {
+    let desc = TestDesc {
+        name: StaticTestName("module::fails_with_<&>"),
+        ignore: false,
+        should_panic: ShouldPanic::No,
+        allow_fail: false,
+        timeout: None,
+        test_type: TestType::UnitTest,
+    };
+
+    let mut out = JunitFormatter::new(OutputLocation::Raw(Vec::new()));
+    let mut st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+    st.total = 1;
+    st.failed = 1;
+
+    out.write_run_start(1, None).unwrap();
+    let result = TrFailedMsg(String::from("expected \"a\" got 'b'"));
+    out.write_result(&desc, &result, None, b"out\n", &st).unwrap();
+    assert!(!out.write_run_finish(&st).unwrap());
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+
+    assert!(s.contains(r#"<testsuite name="test" package="test" id="0" errors="0" failures="1""#));
+    assert!(s.contains(r#"<testcase classname="module" name="fails_with_&lt;&amp;&gt;""#));
+    assert!(s.contains(r#"message="expected &quot;a&quot; got &apos;b&apos;""#));
+    assert!(s.contains("<system-out>out&#10;</system-out>"));
+}
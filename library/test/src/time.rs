@@ -60,6 +60,12 @@ pub fn get_default_test_timeout() -> Instant {
     Instant::now() + Duration::from_secs(TEST_WARN_TIMEOUT_S)
 }
 
+/// Returns the hard time limit of the test: the one set with `#[test_timeout]` if any,
+/// otherwise the one passed with `--test-timeout`.
+pub fn get_test_timeout(desc: &TestDesc, default: Option<Duration>) -> Option<Duration> {
+    desc.timeout.map(Duration::from_secs).or(default)
+}
+
 /// The meassured execution time of a unit test.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TestExecTime(pub Duration);
@@ -120,6 +120,8 @@ pub struct TestDesc {
     pub ignore: bool,
     pub should_panic: options::ShouldPanic,
     pub allow_fail: bool,
+    /// Hard time limit in seconds set by `#[test_timeout]`, overriding `--test-timeout`.
+    pub timeout: Option<u64>,
     pub test_type: TestType,
 }
 
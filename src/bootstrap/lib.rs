@@ -178,6 +178,7 @@ const LLVM_TOOLS: &[&str] = &[
     "llvm-strip", // used to discard symbols from binary files to reduce their size
     "llvm-ar", // used for creating and modifying archive files
     "llvm-dis", // used to disassemble LLVM bitcode
+    "llvm-dwp", // used to package split DWARF objects into `.dwp` files
     "llc",     // used to compile LLVM bytecode
     "opt",     // used to optimize LLVM bytecode
 ];
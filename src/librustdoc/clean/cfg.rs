@@ -8,6 +8,7 @@ use std::mem;
 use std::ops;
 
 use rustc_ast::{LitKind, MetaItem, MetaItemKind, NestedMetaItem};
+use rustc_data_structures::fx::FxHashSet;
 use rustc_feature::Features;
 use rustc_session::parse::ParseSess;
 use rustc_span::symbol::{sym, Symbol};
@@ -42,24 +43,28 @@ pub struct InvalidCfgError {
 }
 
 impl Cfg {
-    /// Parses a `NestedMetaItem` into a `Cfg`.
-    fn parse_nested(nested_cfg: &NestedMetaItem) -> Result<Cfg, InvalidCfgError> {
+    /// Parses a `NestedMetaItem` into a `Cfg`, leaving out the configuration options in
+    /// `exclude`.
+    fn parse_nested(
+        nested_cfg: &NestedMetaItem,
+        exclude: &FxHashSet<Cfg>,
+    ) -> Result<Option<Cfg>, InvalidCfgError> {
         match nested_cfg {
-            NestedMetaItem::MetaItem(ref cfg) => Cfg::parse(cfg),
+            NestedMetaItem::MetaItem(ref cfg) => Cfg::parse_without(cfg, exclude),
             NestedMetaItem::Literal(ref lit) => {
                 Err(InvalidCfgError { msg: "unexpected literal", span: lit.span })
             }
         }
     }
 
-    /// Parses a `MetaItem` into a `Cfg`.
-    ///
-    /// The `MetaItem` should be the content of the `#[cfg(...)]`, e.g., `unix` or
-    /// `target_os = "redox"`.
+    /// Parses a `MetaItem` into a `Cfg`, leaving out the configuration options in `exclude`.
     ///
-    /// If the content is not properly formatted, it will return an error indicating what and where
-    /// the error is.
-    pub fn parse(cfg: &MetaItem) -> Result<Cfg, InvalidCfgError> {
+    /// Returns `Ok(None)` if nothing is left of the configuration, for example for `not(test)`
+    /// when `test` is excluded.
+    crate fn parse_without(
+        cfg: &MetaItem,
+        exclude: &FxHashSet<Cfg>,
+    ) -> Result<Option<Cfg>, InvalidCfgError> {
         let name = match cfg.ident() {
             Some(ident) => ident.name,
             None => {
@@ -69,33 +74,62 @@ impl Cfg {
                 });
             }
         };
-        match cfg.kind {
-            MetaItemKind::Word => Ok(Cfg::Cfg(name, None)),
+        let cfg = match cfg.kind {
+            MetaItemKind::Word => Cfg::Cfg(name, None),
             MetaItemKind::NameValue(ref lit) => match lit.kind {
-                LitKind::Str(value, _) => Ok(Cfg::Cfg(name, Some(value))),
-                _ => Err(InvalidCfgError {
-                    // FIXME: if the main #[cfg] syntax decided to support non-string literals,
-                    // this should be changed as well.
-                    msg: "value of cfg option should be a string literal",
-                    span: lit.span,
-                }),
+                LitKind::Str(value, _) => Cfg::Cfg(name, Some(value)),
+                _ => {
+                    return Err(InvalidCfgError {
+                        // FIXME: if the main #[cfg] syntax decided to support non-string literals,
+                        // this should be changed as well.
+                        msg: "value of cfg option should be a string literal",
+                        span: lit.span,
+                    });
+                }
             },
             MetaItemKind::List(ref items) => {
-                let mut sub_cfgs = items.iter().map(Cfg::parse_nested);
                 match name {
-                    sym::all => sub_cfgs.fold(Ok(Cfg::True), |x, y| Ok(x? & y?)),
-                    sym::any => sub_cfgs.fold(Ok(Cfg::False), |x, y| Ok(x? | y?)),
+                    sym::all | sym::any => {}
+                    sym::not if items.len() == 1 => {}
                     sym::not => {
-                        if sub_cfgs.len() == 1 {
-                            Ok(!sub_cfgs.next().unwrap()?)
-                        } else {
-                            Err(InvalidCfgError { msg: "expected 1 cfg-pattern", span: cfg.span })
-                        }
+                        return Err(InvalidCfgError {
+                            msg: "expected 1 cfg-pattern",
+                            span: cfg.span,
+                        });
                     }
-                    _ => Err(InvalidCfgError { msg: "invalid predicate", span: cfg.span }),
+                    _ => return Err(InvalidCfgError { msg: "invalid predicate", span: cfg.span }),
+                }
+                let mut sub_cfgs = Vec::with_capacity(items.len());
+                for item in items {
+                    if let Some(sub_cfg) = Cfg::parse_nested(item, exclude)? {
+                        sub_cfgs.push(sub_cfg);
+                    }
+                }
+                // Everything in the list was excluded.
+                if sub_cfgs.is_empty() && !items.is_empty() {
+                    return Ok(None);
+                }
+                let mut sub_cfgs = sub_cfgs.into_iter();
+                match name {
+                    sym::all => sub_cfgs.fold(Cfg::True, |x, y| x & y),
+                    sym::any => sub_cfgs.fold(Cfg::False, |x, y| x | y),
+                    // This is `not`, which has a single sub-configuration.
+                    _ => !sub_cfgs.next().unwrap(),
                 }
             }
-        }
+        };
+        if exclude.contains(&cfg) { Ok(None) } else { Ok(Some(cfg)) }
+    }
+
+    /// Parses a `MetaItem` into a `Cfg`.
+    ///
+    /// The `MetaItem` should be the content of the `#[cfg(...)]`, e.g., `unix` or
+    /// `target_os = "redox"`.
+    ///
+    /// If the content is not properly formatted, it will return an error indicating what and where
+    /// the error is.
+    pub fn parse(cfg: &MetaItem) -> Result<Cfg, InvalidCfgError> {
+        Cfg::parse_without(cfg, &FxHashSet::default()).map(|cfg| cfg.unwrap())
     }
 
     /// Checks whether the given configuration can be matched in the current session.
@@ -301,6 +301,40 @@ fn test_parse_err() {
     })
 }
 
+#[test]
+fn test_parse_without() {
+    with_default_session_globals(|| {
+        let mut exclude = FxHashSet::default();
+        exclude.insert(word_cfg("test"));
+        exclude.insert(name_value_cfg("feature", "hidden"));
+
+        let mi = dummy_meta_item_word("test");
+        assert_eq!(Cfg::parse_without(&mi, &exclude), Ok(None));
+
+        let mi = attr::mk_name_value_item_str(
+            Ident::from_str("feature"),
+            Symbol::intern("hidden"),
+            DUMMY_SP,
+        );
+        assert_eq!(Cfg::parse_without(&mi, &exclude), Ok(None));
+
+        let mi = dummy_meta_item_list!(not, [test]);
+        assert_eq!(Cfg::parse_without(&mi, &exclude), Ok(None));
+
+        let mi = dummy_meta_item_list!(any, [test]);
+        assert_eq!(Cfg::parse_without(&mi, &exclude), Ok(None));
+
+        let mi = dummy_meta_item_list!(all, [unix, test]);
+        assert_eq!(Cfg::parse_without(&mi, &exclude), Ok(Some(word_cfg("unix"))));
+
+        let mi = dummy_meta_item_list!(not, [dummy_meta_item_list!(any, [unix, test]),]);
+        assert_eq!(Cfg::parse_without(&mi, &exclude), Ok(Some(!word_cfg("unix"))));
+
+        let mi = dummy_meta_item_list!(not, [test, unix]);
+        assert!(Cfg::parse_without(&mi, &exclude).is_err());
+    })
+}
+
 #[test]
 fn test_render_short_html() {
     with_default_session_globals(|| {
@@ -435,15 +435,18 @@ impl Clean<Lifetime> for hir::GenericParam<'_> {
 }
 
 impl Clean<Constant> for hir::ConstArg {
+    fn clean(&self, cx: &DocContext<'_>) -> Constant {
+        self.value.clean(cx)
+    }
+}
+
+impl Clean<Constant> for hir::AnonConst {
     fn clean(&self, cx: &DocContext<'_>) -> Constant {
         Constant {
-            type_: cx
-                .tcx
-                .type_of(cx.tcx.hir().body_owner_def_id(self.value.body).to_def_id())
-                .clean(cx),
-            expr: print_const_expr(cx, self.value.body),
+            type_: cx.tcx.type_of(cx.tcx.hir().body_owner_def_id(self.body).to_def_id()).clean(cx),
+            expr: print_const_expr(cx, self.body),
             value: None,
-            is_literal: is_literal_expr(cx, self.value.body.hir_id),
+            is_literal: is_literal_expr(cx, self.body.hir_id),
         }
     }
 }
@@ -596,11 +599,16 @@ impl Clean<GenericParamDef> for ty::GenericParamDef {
                     },
                 )
             }
-            ty::GenericParamDefKind::Const { .. } => (
+            ty::GenericParamDefKind::Const { has_default } => (
                 self.name.clean(cx),
                 GenericParamDefKind::Const {
                     did: self.def_id,
                     ty: cx.tcx.type_of(self.def_id).clean(cx),
+                    default: if has_default {
+                        Some(print_const(cx, cx.tcx.const_param_default(self.def_id)))
+                    } else {
+                        None
+                    },
                 },
             ),
         };
@@ -638,11 +646,12 @@ impl Clean<GenericParamDef> for hir::GenericParam<'_> {
                     synthetic,
                 },
             ),
-            hir::GenericParamKind::Const { ref ty } => (
+            hir::GenericParamKind::Const { ref ty, default } => (
                 self.name.ident().name.clean(cx),
                 GenericParamDefKind::Const {
                     did: cx.tcx.hir().local_def_id(self.hir_id).to_def_id(),
                     ty: ty.clean(cx),
+                    default: default.map(|ct| print_const_expr(cx, ct.body)),
                 },
             ),
         };
@@ -1461,7 +1470,7 @@ impl Clean<Type> for hir::Ty<'_> {
                                     }
                                     indices.types += 1;
                                 }
-                                hir::GenericParamKind::Const { .. } => {
+                                hir::GenericParamKind::Const { ref default, .. } => {
                                     let const_param_def_id =
                                         cx.tcx.hir().local_def_id(param.hir_id);
                                     let mut j = 0;
@@ -1479,8 +1488,12 @@ impl Clean<Type> for hir::Ty<'_> {
                                     if let Some(ct) = const_ {
                                         ct_substs
                                             .insert(const_param_def_id.to_def_id(), ct.clean(cx));
+                                    } else if let Some(default) = default {
+                                        ct_substs.insert(
+                                            const_param_def_id.to_def_id(),
+                                            default.clean(cx),
+                                        );
                                     }
-                                    // FIXME(const_generics:defaults)
                                     indices.consts += 1;
                                 }
                             }
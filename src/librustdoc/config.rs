@@ -27,6 +27,7 @@ use crate::html::render::StylePath;
 use crate::html::static_files;
 use crate::opts;
 use crate::passes::{self, Condition, DefaultPassOption};
+use crate::scrape_examples::{AllCallLocations, FnCallLocations, ScrapeExamplesOptions};
 use crate::theme;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -78,6 +79,8 @@ pub struct Options {
     pub extern_strs: Vec<String>,
     /// List of `cfg` flags to hand to the compiler. Always includes `rustdoc`.
     pub cfgs: Vec<String>,
+    /// List of check cfg flags to hand to the compiler.
+    pub check_cfgs: Vec<String>,
     /// Codegen options to hand to the compiler.
     pub codegen_options: CodegenOptions,
     /// Codegen options strings to hand to the compiler.
@@ -117,6 +120,12 @@ pub struct Options {
     /// For example, using ignore-foo to ignore running the doctest on any target that
     /// contains "foo" as a substring
     pub enable_per_target_ignores: bool,
+    /// Whether to compile the doctests that can be merged into a single binary per edition,
+    /// instead of compiling every doctest on its own.
+    pub merge_doctests: bool,
+    /// If present, the calls to the functions of other crates are looked for in the crate, and
+    /// written to a file to be shown in the documentation of these functions.
+    pub scrape_examples_options: Option<ScrapeExamplesOptions>,
 
     /// The path to a rustc-like binary to build tests with. If not set, we
     /// default to loading from $sysroot/bin/rustc.
@@ -165,6 +174,7 @@ impl fmt::Debug for Options {
             .field("libs", &self.libs)
             .field("externs", &FmtExterns(&self.externs))
             .field("cfgs", &self.cfgs)
+            .field("check_cfgs", &self.check_cfgs)
             .field("codegen_options", &"...")
             .field("debugging_options", &"...")
             .field("target", &self.target)
@@ -185,6 +195,8 @@ impl fmt::Debug for Options {
             .field("runtool", &self.runtool)
             .field("runtool_args", &self.runtool_args)
             .field("enable-per-target-ignores", &self.enable_per_target_ignores)
+            .field("merge_doctests", &self.merge_doctests)
+            .field("scrape_examples_options", &self.scrape_examples_options)
             .finish()
     }
 }
@@ -250,6 +262,11 @@ pub struct RenderOptions {
     pub document_private: bool,
     /// Document items that have `doc(hidden)`.
     pub document_hidden: bool,
+    /// Whether identifiers in the rendered source files link to their definitions.
+    pub generate_link_to_definition: bool,
+    /// The calls to the functions of the crate that were scraped from other crates, and which
+    /// are shown in the documentation of these functions.
+    pub call_locations: AllCallLocations,
 }
 
 /// Temporary storage for data obtained during `RustdocVisitor::clean()`.
@@ -264,6 +281,11 @@ pub struct RenderInfo {
     pub deref_mut_trait_did: Option<DefId>,
     pub owned_box_did: Option<DefId>,
     pub output_format: Option<OutputFormat>,
+    /// The links from identifiers in the local source files to their definitions, collected
+    /// when `--generate-link-to-definition` is passed.
+    pub span_map: crate::html::render::span_map::SpanMap,
+    /// The calls to the local functions that were given with `--with-examples`.
+    pub call_locations: FxHashMap<DefId, FnCallLocations>,
 }
 
 impl Options {
@@ -383,6 +405,7 @@ impl Options {
         let output =
             matches.opt_str("o").map(|s| PathBuf::from(&s)).unwrap_or_else(|| PathBuf::from("doc"));
         let cfgs = matches.opt_strs("cfg");
+        let check_cfgs = matches.opt_strs("check-cfg");
 
         let extension_css = matches.opt_str("e").map(|s| PathBuf::from(&s));
 
@@ -526,6 +549,20 @@ impl Options {
             },
             None => None,
         };
+
+        let generate_link_to_definition = matches.opt_present("generate-link-to-definition");
+        if generate_link_to_definition && output_format.map_or(false, |o| o.is_json()) {
+            diag.struct_err(
+                "--generate-link-to-definition option can only be used with HTML output format",
+            )
+            .emit();
+            return Err(1);
+        }
+
+        let scrape_examples_options = ScrapeExamplesOptions::new(matches, &diag)?;
+        let call_locations =
+            crate::scrape_examples::load_call_locations(matches.opt_strs("with-examples"), &diag)?;
+
         let crate_name = matches.opt_str("crate-name");
         let proc_macro_crate = crate_types.contains(&CrateType::ProcMacro);
         let playground_url = matches.opt_str("playground-url");
@@ -550,6 +587,7 @@ impl Options {
         let runtool = matches.opt_str("runtool");
         let runtool_args = matches.opt_strs("runtool-arg");
         let enable_per_target_ignores = matches.opt_present("enable-per-target-ignores");
+        let merge_doctests = matches.opt_present("merge-doctests");
         let document_private = matches.opt_present("document-private-items");
         let document_hidden = matches.opt_present("document-hidden-items");
 
@@ -565,6 +603,7 @@ impl Options {
             externs,
             extern_strs,
             cfgs,
+            check_cfgs,
             codegen_options,
             codegen_options_strs,
             debugging_opts,
@@ -586,6 +625,8 @@ impl Options {
             runtool,
             runtool_args,
             enable_per_target_ignores,
+            merge_doctests,
+            scrape_examples_options,
             test_builder,
             render_options: RenderOptions {
                 output,
@@ -607,6 +648,8 @@ impl Options {
                 generate_search_filter,
                 document_private,
                 document_hidden,
+                generate_link_to_definition,
+                call_locations,
             },
             output_format,
         })
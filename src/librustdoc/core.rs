@@ -4,6 +4,7 @@ use rustc_data_structures::sync::{self, Lrc};
 use rustc_driver::abort_on_err;
 use rustc_errors::emitter::{Emitter, EmitterWriter};
 use rustc_errors::json::JsonEmitter;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_feature::UnstableFeatures;
 use rustc_hir::def::{Namespace::TypeNS, Res};
 use rustc_hir::def_id::{CrateNum, DefId, DefIndex, LocalDefId, CRATE_DEF_INDEX, LOCAL_CRATE};
@@ -34,7 +35,9 @@ use crate::clean;
 use crate::clean::{AttributesExt, MAX_DEF_ID};
 use crate::config::{Options as RustdocOptions, RenderOptions};
 use crate::config::{OutputFormat, RenderInfo};
+use crate::html::render::span_map;
 use crate::passes::{self, Condition::*, ConditionalPass};
+use crate::scrape_examples::{self, ScrapeExamplesOptions};
 
 pub use rustc_session::config::{CodegenOptions, DebuggingOptions, Input, Options};
 pub use rustc_session::search_paths::SearchPath;
@@ -176,8 +179,8 @@ impl<'tcx> DocContext<'tcx> {
 
 /// Creates a new diagnostic `Handler` that can be used to emit warnings and errors.
 ///
-/// If the given `error_format` is `ErrorOutputType::Json` and no `SourceMap` is given, a new one
-/// will be created for the handler.
+/// If the given `error_format` is `ErrorOutputType::Json` or `ErrorOutputType::Sarif` and no
+/// `SourceMap` is given, a new one will be created for the handler.
 pub fn new_handler(
     error_format: ErrorOutputType,
     source_map: Option<Lrc<source_map::SourceMap>>,
@@ -214,6 +217,12 @@ pub fn new_handler(
                 .ui_testing(debugging_opts.ui_testing),
             )
         }
+        ErrorOutputType::Sarif => {
+            let source_map = source_map.unwrap_or_else(|| {
+                Lrc::new(source_map::SourceMap::new(source_map::FilePathMapping::empty()))
+            });
+            Box::new(SarifEmitter::stderr(source_map))
+        }
     };
 
     rustc_errors::Handler::with_emitter_and_flags(
@@ -293,6 +302,7 @@ pub fn run_core(
         libs,
         externs,
         mut cfgs,
+        check_cfgs,
         codegen_options,
         debugging_opts,
         target,
@@ -306,6 +316,7 @@ pub fn run_core(
         display_warnings,
         render_options,
         output_format,
+        scrape_examples_options,
         ..
     } = options;
 
@@ -380,6 +391,7 @@ pub fn run_core(
     let config = interface::Config {
         opts: sessopts,
         crate_cfg: interface::parse_cfgspecs(cfgs),
+        crate_check_cfg: interface::parse_check_cfg(check_cfgs),
         input,
         input_path: cpath,
         output_file: None,
@@ -476,6 +488,7 @@ pub fn run_core(
                         manual_passes,
                         render_options,
                         output_format,
+                        scrape_examples_options,
                     )
                 })
             });
@@ -489,8 +502,9 @@ fn run_global_ctxt(
     resolver: Rc<RefCell<interface::BoxedResolver>>,
     mut default_passes: passes::DefaultPassOption,
     mut manual_passes: Vec<String>,
-    render_options: RenderOptions,
+    mut render_options: RenderOptions,
     output_format: Option<OutputFormat>,
+    scrape_examples_options: Option<ScrapeExamplesOptions>,
 ) -> (clean::Crate, RenderInfo, RenderOptions) {
     // Certain queries assume that some checks were run elsewhere
     // (see https://github.com/rust-lang/rust/pull/73566#issuecomment-656954425),
@@ -534,6 +548,16 @@ fn run_global_ctxt(
     let mut renderinfo = RenderInfo::default();
     renderinfo.access_levels = access_levels;
     renderinfo.output_format = output_format;
+    if render_options.generate_link_to_definition {
+        renderinfo.span_map = tcx.sess.time("collect_spans", || span_map::collect_spans(tcx));
+    }
+    if !render_options.call_locations.is_empty() {
+        let call_locations = mem::take(&mut render_options.call_locations);
+        renderinfo.call_locations = scrape_examples::local_call_locations(tcx, call_locations);
+    }
+    if let Some(ref options) = scrape_examples_options {
+        tcx.sess.time("scrape_examples", || scrape_examples::run(tcx, options));
+    }
 
     let mut ctxt = DocContext {
         tcx,
@@ -21,9 +21,10 @@ use std::collections::HashMap;
 use std::env;
 use std::io::{self, Write};
 use std::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
+use std::sync::{Arc, Mutex};
 
 use crate::clean::Attributes;
 use crate::config::Options;
@@ -84,6 +85,7 @@ pub fn run(options: Options) -> Result<(), ErrorReported> {
     let config = interface::Config {
         opts: sessopts,
         crate_cfg: interface::parse_cfgspecs(cfgs),
+        crate_check_cfg: interface::parse_check_cfg(options.check_cfgs.clone()),
         input,
         input_path: None,
         output_file: None,
@@ -253,47 +255,16 @@ fn run_test(
 
     let output_file = outdir.path().join("rust_out");
 
-    let rustc_binary = options
-        .test_builder
-        .as_deref()
-        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
-    let mut compiler = Command::new(&rustc_binary);
-    compiler.arg("--crate-type").arg("bin");
-    for cfg in &options.cfgs {
-        compiler.arg("--cfg").arg(&cfg);
-    }
-    if let Some(sysroot) = options.maybe_sysroot {
-        compiler.arg("--sysroot").arg(sysroot);
-    }
-    compiler.arg("--edition").arg(&edition.to_string());
+    let mut compiler = make_rustc_command(&options, &target, edition);
     compiler.env("UNSTABLE_RUSTDOC_TEST_PATH", path);
     compiler.env("UNSTABLE_RUSTDOC_TEST_LINE", format!("{}", line as isize - line_offset as isize));
     compiler.arg("-o").arg(&output_file);
     if as_test_harness {
         compiler.arg("--test");
     }
-    for lib_str in &options.lib_strs {
-        compiler.arg("-L").arg(&lib_str);
-    }
-    for extern_str in &options.extern_strs {
-        compiler.arg("--extern").arg(&extern_str);
-    }
-    compiler.arg("-Ccodegen-units=1");
-    for codegen_options_str in &options.codegen_options_strs {
-        compiler.arg("-C").arg(&codegen_options_str);
-    }
-    for debugging_option_str in &options.debugging_opts_strs {
-        compiler.arg("-Z").arg(&debugging_option_str);
-    }
     if no_run && !compile_fail {
         compiler.arg("--emit=metadata");
     }
-    compiler.arg("--target").arg(match target {
-        TargetTriple::TargetTriple(s) => s,
-        TargetTriple::TargetPath(path) => {
-            path.to_str().expect("target path must be valid unicode").to_string()
-        }
-    });
 
     compiler.arg("-");
     compiler.stdin(Stdio::piped());
@@ -338,15 +309,7 @@ fn run_test(
     }
 
     // Run the code!
-    let mut cmd;
-
-    if let Some(tool) = runtool {
-        cmd = Command::new(tool);
-        cmd.args(runtool_args);
-        cmd.arg(output_file);
-    } else {
-        cmd = Command::new(output_file);
-    }
+    let mut cmd = make_run_command(runtool, runtool_args, &output_file);
 
     match cmd.output() {
         Err(e) => return Err(TestFailure::ExecutionError(e)),
@@ -362,6 +325,66 @@ fn run_test(
     Ok(())
 }
 
+/// Returns a command that invokes rustc to compile a doctest into a binary, with the options
+/// passed to rustdoc.
+fn make_rustc_command(options: &Options, target: &TargetTriple, edition: Edition) -> Command {
+    let rustc_binary = options
+        .test_builder
+        .as_deref()
+        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
+    let mut compiler = Command::new(&rustc_binary);
+    compiler.arg("--crate-type").arg("bin");
+    for cfg in &options.cfgs {
+        compiler.arg("--cfg").arg(&cfg);
+    }
+    if !options.check_cfgs.is_empty() {
+        compiler.arg("-Z").arg("unstable-options");
+        for check_cfg in &options.check_cfgs {
+            compiler.arg("--check-cfg").arg(&check_cfg);
+        }
+    }
+    if let Some(sysroot) = &options.maybe_sysroot {
+        compiler.arg("--sysroot").arg(sysroot);
+    }
+    compiler.arg("--edition").arg(&edition.to_string());
+    for lib_str in &options.lib_strs {
+        compiler.arg("-L").arg(&lib_str);
+    }
+    for extern_str in &options.extern_strs {
+        compiler.arg("--extern").arg(&extern_str);
+    }
+    compiler.arg("-Ccodegen-units=1");
+    for codegen_options_str in &options.codegen_options_strs {
+        compiler.arg("-C").arg(&codegen_options_str);
+    }
+    for debugging_option_str in &options.debugging_opts_strs {
+        compiler.arg("-Z").arg(&debugging_option_str);
+    }
+    compiler.arg("--target").arg(match target {
+        TargetTriple::TargetTriple(s) => s.clone(),
+        TargetTriple::TargetPath(path) => {
+            path.to_str().expect("target path must be valid unicode").to_string()
+        }
+    });
+    compiler
+}
+
+/// Returns a command that runs the doctest binary `output_file`, through `runtool` if given.
+fn make_run_command(
+    runtool: Option<String>,
+    runtool_args: Vec<String>,
+    output_file: &Path,
+) -> Command {
+    if let Some(tool) = runtool {
+        let mut cmd = Command::new(tool);
+        cmd.args(runtool_args);
+        cmd.arg(output_file);
+        cmd
+    } else {
+        Command::new(output_file)
+    }
+}
+
 /// Transforms a test into code that can be compiled into a Rust binary, and returns the number of
 /// lines before the test code begins.
 pub fn make_test(
@@ -396,6 +419,49 @@ pub fn make_test(
     prog.push_str(&crate_attrs);
     prog.push_str(&crates);
 
+    let (already_has_main, already_has_extern_crate) =
+        match check_for_main_and_extern_crate(s, crates + everything_else, cratename, edition) {
+            Ok(result) => result,
+            Err(ErrorReported) => {
+                // If the parser panicked due to a fatal error, pass the test code through
+                // unchanged. The error will be reported during compilation.
+                return (s.to_owned(), 0);
+            }
+        };
+
+    // Don't inject `extern crate std` because it's already injected by the
+    // compiler.
+    if !already_has_extern_crate && !opts.no_crate_inject && cratename != Some("std") {
+        if let Some(cratename) = cratename {
+            // Make sure its actually used if not included.
+            if s.contains(cratename) {
+                prog.push_str(&format!("extern crate {};\n", cratename));
+                line_offset += 1;
+            }
+        }
+    }
+
+    // FIXME: This code cannot yet handle no_std test cases yet
+    if dont_insert_main || already_has_main || prog.contains("![no_std]") {
+        prog.push_str(everything_else);
+    } else {
+        push_wrapped_in_main(&mut prog, everything_else);
+        line_offset += 1;
+    }
+
+    debug!("final doctest:\n{}", prog);
+
+    (prog, line_offset)
+}
+
+/// Parses the `source` of the doctest `s`, and returns whether it already has a `fn main` and an
+/// `extern crate` item for `cratename`.
+fn check_for_main_and_extern_crate(
+    s: &str,
+    source: String,
+    cratename: Option<&str>,
+    edition: Edition,
+) -> Result<(bool, bool), ErrorReported> {
     // Uses librustc_ast to parse the doctest and find if there's a main fn and the extern
     // crate already is included.
     let result = rustc_driver::catch_fatal_errors(|| {
@@ -407,7 +473,6 @@ pub fn make_test(
             use rustc_span::source_map::FilePathMapping;
 
             let filename = FileName::anon_source_code(s);
-            let source = crates + everything_else;
 
             // Any errors in parsing should also appear when the doctest is compiled for real, so just
             // send all the errors that librustc_ast emits directly into a `Sink` instead of stderr.
@@ -478,14 +543,7 @@ pub fn make_test(
             (found_main, found_extern_crate, found_macro)
         })
     });
-    let (already_has_main, already_has_extern_crate, found_macro) = match result {
-        Ok(result) => result,
-        Err(ErrorReported) => {
-            // If the parser panicked due to a fatal error, pass the test code through unchanged.
-            // The error will be reported during compilation.
-            return (s.to_owned(), 0);
-        }
-    };
+    let (already_has_main, already_has_extern_crate, found_macro) = result?;
 
     // If a doctest's `fn main` is being masked by a wrapper macro, the parsing loop above won't
     // see it. In that case, run the old text-based scan to see if they at least have a main
@@ -502,38 +560,21 @@ pub fn make_test(
         already_has_main
     };
 
-    // Don't inject `extern crate std` because it's already injected by the
-    // compiler.
-    if !already_has_extern_crate && !opts.no_crate_inject && cratename != Some("std") {
-        if let Some(cratename) = cratename {
-            // Make sure its actually used if not included.
-            if s.contains(cratename) {
-                prog.push_str(&format!("extern crate {};\n", cratename));
-                line_offset += 1;
-            }
-        }
-    }
+    Ok((already_has_main, already_has_extern_crate))
+}
 
-    // FIXME: This code cannot yet handle no_std test cases yet
-    if dont_insert_main || already_has_main || prog.contains("![no_std]") {
-        prog.push_str(everything_else);
+/// Pushes `code` to `prog`, wrapped in a `fn main`.
+fn push_wrapped_in_main(prog: &mut String, code: &str) {
+    let returns_result = code.trim_end().ends_with("(())");
+    let (main_pre, main_post) = if returns_result {
+        (
+            "fn main() { fn _inner() -> Result<(), impl core::fmt::Debug> {",
+            "}\n_inner().unwrap() }",
+        )
     } else {
-        let returns_result = everything_else.trim_end().ends_with("(())");
-        let (main_pre, main_post) = if returns_result {
-            (
-                "fn main() { fn _inner() -> Result<(), impl core::fmt::Debug> {",
-                "}\n_inner().unwrap() }",
-            )
-        } else {
-            ("fn main() {\n", "\n}")
-        };
-        prog.extend([main_pre, everything_else, main_post].iter().cloned());
-        line_offset += 1;
-    }
-
-    debug!("final doctest:\n{}", prog);
-
-    (prog, line_offset)
+        ("fn main() {\n", "\n}")
+    };
+    prog.extend([main_pre, code, main_post].iter().cloned());
 }
 
 // FIXME(aburka): use a real parser to deal with multiline attributes
@@ -606,6 +647,359 @@ fn partition_source(s: &str) -> (String, String, String) {
     (before, after, crates)
 }
 
+/// A doctest that is compiled as part of a `DocTestBundle`.
+#[derive(Debug, PartialEq)]
+struct MergedTest {
+    /// The `extern crate` items of the doctest, which are moved to the root of the bundle.
+    crates: Vec<String>,
+    /// Whether the doctest needs an `extern crate` item for the crate being tested.
+    inject_crate: bool,
+    /// The code of the doctest, with a `fn main`, preceded by blank lines so that it starts at the
+    /// same line as in the file that the doctest is in.
+    code: String,
+    /// The path to the file that the doctest is in.
+    path: PathBuf,
+}
+
+impl MergedTest {
+    /// Returns the path, relative to the bundle, of the file that the module of the doctest with
+    /// the given index is in.
+    ///
+    /// The file has the same name as the file that the doctest is in, and its directory is
+    /// remapped to the directory of that file, so that the locations of panics in the doctest are
+    /// reported as in the documented file.
+    fn module_file(&self, index: usize) -> String {
+        let file_name = self.path.file_name().map_or_else(
+            || "doctest.rs".into(),
+            |file_name| file_name.to_string_lossy(),
+        );
+        format!("doctest_{}/{}", index, file_name)
+    }
+}
+
+/// Transforms a test into a `MergedTest`, or returns `None` if it has crate attributes and so
+/// cannot be merged with other tests.
+fn make_merged_test(
+    s: &str,
+    cratename: Option<&str>,
+    opts: &TestOptions,
+    edition: Edition,
+    path: PathBuf,
+    line: usize,
+) -> Option<MergedTest> {
+    let (crate_attrs, everything_else, crates) = partition_source(s);
+    if crate_attrs.lines().any(|line| line.trim().starts_with("#![")) {
+        return None;
+    }
+    // The number of lines of the doctest before the code that is kept in its module.
+    let skipped_lines = crate_attrs.lines().count()
+        + crates.lines().count()
+        + everything_else[..everything_else.len() - everything_else.trim_start().len()]
+            .matches('\n')
+            .count();
+    let everything_else = everything_else.trim();
+
+    let (already_has_main, already_has_extern_crate) =
+        check_for_main_and_extern_crate(s, crates.clone() + everything_else, cratename, edition)
+            .ok()?;
+
+    let inject_crate = !already_has_extern_crate
+        && !opts.no_crate_inject
+        && cratename != Some("std")
+        && cratename.map_or(false, |cratename| s.contains(cratename));
+
+    // The code of the doctest starts at the line after the one of its opening fence.
+    let mut code = String::new();
+    let padding =
+        |wrapper_lines: usize| "\n".repeat((line + skipped_lines).saturating_sub(wrapper_lines));
+    if already_has_main {
+        code.push_str(&padding(0));
+        code.push_str(everything_else);
+    } else {
+        code.push_str(&padding(1));
+        push_wrapped_in_main(&mut code, everything_else);
+    }
+
+    let crates = crates
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .map(str::to_owned)
+        .collect();
+    Some(MergedTest { crates, inject_crate, code, path })
+}
+
+/// Returns the source of the crate that the doctests of `tests` with the given `indices` are
+/// merged into.
+///
+/// Each doctest is compiled into its own module, whose file is returned by
+/// `make_bundle_module`, with its `extern crate` items moved to the crate root. The `fn main` of
+/// the crate runs the doctest whose index is passed in `DOCTEST_INDEX_VAR`, so that each doctest
+/// still runs in its own process.
+fn make_bundle_source(
+    tests: &[MergedTest],
+    indices: &[usize],
+    cratename: Option<&str>,
+    opts: &TestOptions,
+) -> String {
+    let mut prog = String::new();
+
+    if opts.attrs.is_empty() && !opts.display_warnings {
+        prog.push_str("#![allow(unused)]\n");
+    }
+    for attr in &opts.attrs {
+        prog.push_str(&format!("#![{}]\n", attr));
+    }
+
+    let mut crates: Vec<String> = Vec::new();
+    if let Some(cratename) = cratename {
+        if indices.iter().any(|&i| tests[i].inject_crate) {
+            crates.push(format!("extern crate {};", cratename));
+        }
+    }
+    for krate in indices.iter().flat_map(|&i| &tests[i].crates) {
+        if !crates.contains(krate) {
+            crates.push(krate.clone());
+        }
+    }
+    for krate in &crates {
+        prog.push_str(krate);
+        prog.push('\n');
+    }
+
+    // The return type of a `fn main` of a doctest is either `()` or a `Result`, which is
+    // reported like the `Termination` impls of the standard library do.
+    prog.push_str(
+        "\
+trait __DocTestResult {
+    fn __report(self) -> i32;
+}
+impl __DocTestResult for () {
+    fn __report(self) -> i32 { 0 }
+}
+impl<E: ::std::fmt::Debug> __DocTestResult for Result<(), E> {
+    fn __report(self) -> i32 {
+        match self {
+            Ok(()) => 0,
+            Err(err) => {
+                eprintln!(\"Error: {:?}\", err);
+                1
+            }
+        }
+    }
+}
+",
+    );
+
+    for &i in indices {
+        prog.push_str(&format!("#[path = {:?}]\nmod __doctest_{};\n", tests[i].module_file(i), i));
+    }
+
+    prog.push_str("fn main() {\n");
+    // The variable is removed so that the doctest sees the same environment as when it is
+    // compiled on its own.
+    prog.push_str(&format!(
+        "    let test = ::std::env::var({:?}).expect(\"missing doctest index\");\n",
+        DOCTEST_INDEX_VAR
+    ));
+    prog.push_str(&format!("    ::std::env::remove_var({:?});\n", DOCTEST_INDEX_VAR));
+    prog.push_str("    let code = match &*test {\n");
+    for i in indices {
+        prog.push_str(&format!("        \"{0}\" => __doctest_{0}::__main(),\n", i));
+    }
+    prog.push_str("        _ => panic!(\"unknown doctest index `{}`\", test),\n");
+    prog.push_str("    };\n");
+    prog.push_str("    ::std::process::exit(code);\n");
+    prog.push_str("}\n");
+
+    debug!("doctest bundle:\n{}", prog);
+
+    prog
+}
+
+/// The environment variable that the index of the doctest to run is passed in to the binary of a
+/// bundle.
+const DOCTEST_INDEX_VAR: &str = "__RUSTDOC_DOCTEST_INDEX";
+
+/// Returns the source of the module that the doctest `test` is compiled into in its bundle.
+fn make_bundle_module(test: &MergedTest) -> String {
+    let mut prog = test.code.clone();
+    prog.push_str("\npub(super) fn __main() -> i32 {\n");
+    prog.push_str("    super::__DocTestResult::__report(main())\n");
+    prog.push_str("}\n");
+    prog
+}
+
+enum BundleState {
+    /// The doctests that have been added to the bundle so far.
+    Collecting(Vec<MergedTest>),
+    /// The path to the binary of the bundle, or `None` if the bundle failed to compile, and the
+    /// indices of the doctests that were left out of it because they failed to compile in it.
+    Built(Option<PathBuf>, Vec<usize>),
+}
+
+/// A crate that the compatible doctests of one edition are merged into, so that rustc is only
+/// invoked once for all of them.
+///
+/// The bundle is built when the first of its doctests runs. If it fails to compile, the doctests
+/// that fail to compile in it are found by bisection and left out of it, and fall back to being
+/// compiled on their own, which also reports their errors with the right line numbers.
+struct DocTestBundle {
+    cratename: String,
+    opts: TestOptions,
+    options: Options,
+    edition: Edition,
+    outdir: DirState,
+    state: Mutex<BundleState>,
+}
+
+impl DocTestBundle {
+    fn new(
+        cratename: String,
+        opts: TestOptions,
+        options: Options,
+        edition: Edition,
+        outdir: DirState,
+    ) -> DocTestBundle {
+        DocTestBundle {
+            cratename,
+            opts,
+            options,
+            edition,
+            outdir,
+            state: Mutex::new(BundleState::Collecting(Vec::new())),
+        }
+    }
+
+    /// Adds a doctest to the bundle, and returns its index.
+    fn add_test(&self, test: MergedTest) -> usize {
+        match &mut *self.state.lock().unwrap() {
+            BundleState::Collecting(tests) => {
+                tests.push(test);
+                tests.len() - 1
+            }
+            BundleState::Built(..) => panic!("adding a doctest to a bundle that was already built"),
+        }
+    }
+
+    /// Returns the path to the binary of the bundle if it contains the doctest with the given
+    /// index, building it if this was not done yet.
+    fn binary(&self, index: usize) -> Option<PathBuf> {
+        let mut state = self.state.lock().unwrap();
+        if let BundleState::Collecting(tests) = &*state {
+            let (binary, excluded) = self.build(tests);
+            *state = BundleState::Built(binary, excluded);
+        }
+        match &*state {
+            BundleState::Built(_, excluded) if excluded.contains(&index) => None,
+            BundleState::Built(binary, _) => binary.clone(),
+            BundleState::Collecting(_) => unreachable!(),
+        }
+    }
+
+    /// Builds the bundle, leaving out the doctests that fail to compile in it.
+    fn build(&self, tests: &[MergedTest]) -> (Option<PathBuf>, Vec<usize>) {
+        for (i, test) in tests.iter().enumerate() {
+            let module_file = self.outdir.path().join(test.module_file(i));
+            let written = std::fs::create_dir_all(module_file.parent().unwrap())
+                .and_then(|_| std::fs::write(&module_file, make_bundle_module(test)));
+            if written.is_err() {
+                return (None, Vec::new());
+            }
+        }
+
+        let mut indices: Vec<usize> = (0..tests.len()).collect();
+        if let Some(binary) = self.compile(tests, &indices, false) {
+            return (Some(binary), Vec::new());
+        }
+
+        let excluded = self.find_failing(tests, &indices);
+        indices.retain(|index| !excluded.contains(index));
+        let binary = if indices.is_empty() { None } else { self.compile(tests, &indices, false) };
+        (binary, excluded)
+    }
+
+    /// Returns the indices of the doctests among `indices` that fail to compile in the bundle,
+    /// knowing that the bundle of all of them fails to compile.
+    fn find_failing(&self, tests: &[MergedTest], indices: &[usize]) -> Vec<usize> {
+        if indices.len() == 1 {
+            return indices.to_vec();
+        }
+        let (left, right) = indices.split_at(indices.len() / 2);
+        let mut failing = Vec::new();
+        for &half in &[left, right] {
+            if self.compile(tests, half, true).is_none() {
+                failing.extend(self.find_failing(tests, half));
+            }
+        }
+        failing
+    }
+
+    /// Compiles the bundle of the doctests with the given indices, and returns the path to its
+    /// binary, or to its metadata if `check_only` is `true`, or `None` if it failed to compile.
+    fn compile(
+        &self,
+        tests: &[MergedTest],
+        indices: &[usize],
+        check_only: bool,
+    ) -> Option<PathBuf> {
+        let source = make_bundle_source(tests, indices, Some(&self.cratename), &self.opts);
+        let source_file = self.outdir.path().join("doctest_bundle.rs");
+        std::fs::write(&source_file, source).ok()?;
+
+        let output_file = self.outdir.path().join("rust_out");
+        let mut compiler = make_rustc_command(&self.options, &self.options.target, self.edition);
+        for &i in indices {
+            let test = &tests[i];
+            let module_file = self.outdir.path().join(test.module_file(i));
+            let mut remap = module_file.parent().unwrap().as_os_str().to_owned();
+            remap.push("=");
+            remap.push(test.path.parent().unwrap_or_else(|| Path::new("")));
+            compiler.arg("--remap-path-prefix").arg(remap);
+        }
+        if check_only {
+            compiler.arg("--emit=metadata");
+        }
+        compiler.arg("-o").arg(&output_file);
+        compiler.arg(&source_file);
+
+        let output = compiler.output().expect("Failed to spawn rustc process");
+        if output.status.success() {
+            // The warnings of the bundle are shown with the output of the doctest that built it,
+            // and point to the lines of the doctests that they are about.
+            if self.opts.display_warnings && !check_only {
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            }
+            Some(output_file)
+        } else {
+            debug!(
+                "doctest bundle of {:?} failed to compile:\n{}",
+                indices,
+                String::from_utf8_lossy(&output.stderr)
+            );
+            None
+        }
+    }
+
+    /// Runs the doctest with the given index, or returns `None` if it could not be compiled in the
+    /// bundle.
+    fn run_test(&self, index: usize) -> Option<Result<(), TestFailure>> {
+        let binary = self.binary(index)?;
+        let mut cmd = make_run_command(
+            self.options.runtool.clone(),
+            self.options.runtool_args.clone(),
+            &binary,
+        );
+        cmd.env(DOCTEST_INDEX_VAR, index.to_string());
+
+        Some(match cmd.output() {
+            Err(e) => Err(TestFailure::ExecutionError(e)),
+            Ok(out) if !out.status.success() => Err(TestFailure::ExecutionFailure(out)),
+            Ok(_) => Ok(()),
+        })
+    }
+}
+
 pub trait Tester {
     fn add_test(&mut self, test: String, config: LangString, line: usize);
     fn get_line(&self) -> usize {
@@ -648,6 +1042,8 @@ pub struct Collector {
     source_map: Option<Lrc<SourceMap>>,
     filename: Option<PathBuf>,
     visited_tests: HashMap<(String, usize), usize>,
+    /// The bundles that doctests are merged into when `--merge-doctests` is passed, by edition.
+    bundles: HashMap<Edition, Arc<DocTestBundle>>,
 }
 
 impl Collector {
@@ -672,6 +1068,7 @@ impl Collector {
             source_map,
             filename,
             visited_tests: HashMap::new(),
+            bundles: HashMap::new(),
         }
     }
 
@@ -683,6 +1080,36 @@ impl Collector {
         format!("{} - {}(line {})", filename, item_path, line)
     }
 
+    /// Returns the bundle that doctests of the given edition are merged into.
+    fn bundle(&mut self, edition: Edition) -> Arc<DocTestBundle> {
+        if let Some(bundle) = self.bundles.get(&edition) {
+            return bundle.clone();
+        }
+
+        let outdir = if let Some(path) = &self.options.persist_doctests {
+            let path = path.join(format!("doctest_bundle_{}", edition));
+            std::fs::create_dir_all(&path)
+                .expect("Couldn't create directory for doctest executables");
+            DirState::Perm(path)
+        } else {
+            DirState::Temp(
+                TempFileBuilder::new()
+                    .prefix("rustdoctest")
+                    .tempdir()
+                    .expect("rustdoc needs a tempdir"),
+            )
+        };
+        let bundle = Arc::new(DocTestBundle::new(
+            self.cratename.clone(),
+            self.opts.clone(),
+            self.options.clone(),
+            edition,
+            outdir,
+        ));
+        self.bundles.insert(edition, bundle.clone());
+        bundle
+    }
+
     pub fn set_position(&mut self, position: Span) {
         self.position = position;
     }
@@ -718,6 +1145,11 @@ impl Tester for Collector {
         let runtool_args = self.options.runtool_args.clone();
         let target = self.options.target.clone();
         let target_str = target.to_string();
+        let ignore = match config.ignore {
+            Ignore::All => true,
+            Ignore::None => false,
+            Ignore::Some(ref ignores) => ignores.iter().any(|s| target_str.contains(s)),
+        };
 
         // FIXME(#44940): if doctests ever support path remapping, then this filename
         // needs to be the result of `SourceMap::span_to_unmapped_path`.
@@ -726,6 +1158,25 @@ impl Tester for Collector {
             _ => PathBuf::from(r"doctest.rs"),
         };
 
+        // Doctests that are expected to compile and run successfully can be merged with the
+        // other such doctests of the same edition, unless they set crate attributes.
+        let merged = if self.options.merge_doctests
+            && !ignore
+            && !config.should_panic
+            && !config.no_run
+            && !config.test_harness
+            && !config.compile_fail
+        {
+            make_merged_test(&test, Some(&*cratename), &opts, edition, path.clone(), line)
+        } else {
+            None
+        };
+        let bundled = merged.map(|merged| {
+            let bundle = self.bundle(edition);
+            let index = bundle.add_test(merged);
+            (bundle, index)
+        });
+
         let outdir = if let Some(mut path) = options.persist_doctests.clone() {
             // For example `module/file.rs` would become `module_file_rs`
             let folder_name = filename
@@ -762,38 +1213,54 @@ impl Tester for Collector {
         };
 
         debug!("creating test {}: {}", name, test);
+        let test_name = name.clone();
         self.tests.push(testing::TestDescAndFn {
             desc: testing::TestDesc {
                 name: testing::DynTestName(name),
-                ignore: match config.ignore {
-                    Ignore::All => true,
-                    Ignore::None => false,
-                    Ignore::Some(ref ignores) => ignores.iter().any(|s| target_str.contains(s)),
-                },
+                ignore,
                 // compiler failures are test failures
                 should_panic: testing::ShouldPanic::No,
                 allow_fail: config.allow_fail,
+                timeout: None,
                 test_type: testing::TestType::DocTest,
             },
             testfn: testing::DynTestFn(box move || {
-                let res = run_test(
-                    &test,
-                    &cratename,
-                    line,
-                    options,
-                    config.should_panic,
-                    config.no_run,
-                    config.test_harness,
-                    runtool,
-                    runtool_args,
-                    target,
-                    config.compile_fail,
-                    config.error_codes,
-                    &opts,
-                    edition,
-                    outdir,
-                    path,
-                );
+                let bundled_res = bundled.and_then(|(bundle, index)| {
+                    let res = bundle.run_test(index);
+                    if res.is_none() {
+                        // The output of the test is captured, so this is written to the standard
+                        // error directly, for the fallback not to go unnoticed.
+                        let _ = writeln!(
+                            io::stderr(),
+                            "warning: doctest `{}` failed to compile with the other merged \
+                             doctests, and is compiled on its own",
+                            test_name,
+                        );
+                    }
+                    res
+                });
+                let res = match bundled_res {
+                    Some(res) => res,
+                    // The test was not merged, or failed to compile with the other merged tests.
+                    None => run_test(
+                        &test,
+                        &cratename,
+                        line,
+                        options,
+                        config.should_panic,
+                        config.no_run,
+                        config.test_harness,
+                        runtool,
+                        runtool_args,
+                        target,
+                        config.compile_fail,
+                        config.error_codes,
+                        &opts,
+                        edition,
+                        outdir,
+                        path,
+                    ),
+                };
 
                 if let Err(err) = res {
                     match err {
@@ -1,5 +1,8 @@
-use super::{make_test, TestOptions};
+use super::{
+    make_bundle_module, make_bundle_source, make_merged_test, make_test, MergedTest, TestOptions,
+};
 use rustc_span::edition::DEFAULT_EDITION;
+use std::path::PathBuf;
 
 #[test]
 fn make_test_basic() {
@@ -277,3 +280,164 @@ test_wrapper! {
     let output = make_test(input, Some("my_crate"), false, &opts, DEFAULT_EDITION);
     assert_eq!(output, (expected, 1));
 }
+
+#[test]
+fn make_merged_test_basic() {
+    // Merged tests get a `fn main` like standalone ones, but no crate attributes.
+    let opts = TestOptions::default();
+    let input = "use asdf::qwop;
+assert_eq!(2+2, 4);";
+    let expected = MergedTest {
+        crates: vec![],
+        inject_crate: true,
+        code: "fn main() {
+use asdf::qwop;
+assert_eq!(2+2, 4);
+}"
+        .to_string(),
+        path: PathBuf::from("src/lib.rs"),
+    };
+    let output =
+        make_merged_test(input, Some("asdf"), &opts, DEFAULT_EDITION, "src/lib.rs".into(), 0);
+    assert_eq!(output, Some(expected));
+}
+
+#[test]
+fn make_merged_test_extern_crate() {
+    // The `extern crate` items of a merged test are kept apart, to be moved to the crate root.
+    let opts = TestOptions::default();
+    let input = "#[macro_use] extern crate asdf;
+// a comment
+use asdf::qwop;
+fn main() {}";
+    let expected = MergedTest {
+        crates: vec!["#[macro_use] extern crate asdf;".to_string()],
+        inject_crate: false,
+        code: "\n\nuse asdf::qwop;
+fn main() {}"
+            .to_string(),
+        path: PathBuf::from("src/lib.rs"),
+    };
+    let output =
+        make_merged_test(input, Some("asdf"), &opts, DEFAULT_EDITION, "src/lib.rs".into(), 0);
+    assert_eq!(output, Some(expected));
+}
+
+#[test]
+fn make_merged_test_crate_attrs() {
+    // Tests with crate attributes cannot be merged.
+    let opts = TestOptions::default();
+    let input = "#![feature(asdf)]
+assert_eq!(2+2, 4);";
+    let output =
+        make_merged_test(input, Some("asdf"), &opts, DEFAULT_EDITION, "src/lib.rs".into(), 0);
+    assert_eq!(output, None);
+}
+
+#[test]
+fn make_merged_test_line() {
+    // The code of a merged test starts at the line after the opening fence of the doctest, so
+    // that its module reports the same lines as the documented file.
+    let opts = TestOptions::default();
+    let input = "
+assert_eq!(2+2, 4);";
+    let expected = MergedTest {
+        crates: vec![],
+        inject_crate: false,
+        code: "\n\n\nfn main() {
+assert_eq!(2+2, 4);
+}"
+        .to_string(),
+        path: PathBuf::from("src/lib.rs"),
+    };
+    let output =
+        make_merged_test(input, Some("asdf"), &opts, DEFAULT_EDITION, "src/lib.rs".into(), 3);
+    assert_eq!(output, Some(expected));
+}
+
+#[test]
+fn make_bundle_source_basic() {
+    let opts = TestOptions::default();
+    let tests = [
+        MergedTest {
+            crates: vec![],
+            inject_crate: true,
+            code: "fn main() {\nasdf::foo();\n}".to_string(),
+            path: PathBuf::from("src/lib.rs"),
+        },
+        MergedTest {
+            crates: vec!["extern crate asdf;".to_string()],
+            inject_crate: false,
+            code: "fn main() {}".to_string(),
+            path: PathBuf::from("README.md"),
+        },
+    ];
+    let expected = "#![allow(unused)]
+extern crate asdf;
+trait __DocTestResult {
+    fn __report(self) -> i32;
+}
+impl __DocTestResult for () {
+    fn __report(self) -> i32 { 0 }
+}
+impl<E: ::std::fmt::Debug> __DocTestResult for Result<(), E> {
+    fn __report(self) -> i32 {
+        match self {
+            Ok(()) => 0,
+            Err(err) => {
+                eprintln!(\"Error: {:?}\", err);
+                1
+            }
+        }
+    }
+}
+#[path = \"doctest_0/lib.rs\"]
+mod __doctest_0;
+#[path = \"doctest_1/README.md\"]
+mod __doctest_1;
+fn main() {
+    let test = ::std::env::var(\"__RUSTDOC_DOCTEST_INDEX\").expect(\"missing doctest index\");
+    ::std::env::remove_var(\"__RUSTDOC_DOCTEST_INDEX\");
+    let code = match &*test {
+        \"0\" => __doctest_0::__main(),
+        \"1\" => __doctest_1::__main(),
+        _ => panic!(\"unknown doctest index `{}`\", test),
+    };
+    ::std::process::exit(code);
+}
+";
+    let output = make_bundle_source(&tests, &[0, 1], Some("asdf"), &opts);
+    assert_eq!(output, expected);
+
+    let expected = "fn main() {}
+pub(super) fn __main() -> i32 {
+    super::__DocTestResult::__report(main())
+}
+";
+    assert_eq!(make_bundle_module(&tests[1]), expected);
+}
+
+#[test]
+fn make_bundle_source_subset() {
+    // The doctests that are left out of a bundle have no module in it.
+    let opts = TestOptions::default();
+    let tests = [
+        MergedTest {
+            crates: vec![],
+            inject_crate: true,
+            code: "fn main() {\nasdf::foo();\n}".to_string(),
+            path: PathBuf::from("src/lib.rs"),
+        },
+        MergedTest {
+            crates: vec![],
+            inject_crate: false,
+            code: "fn main() {}".to_string(),
+            path: PathBuf::from("src/lib.rs"),
+        },
+    ];
+    let output = make_bundle_source(&tests, &[1], Some("asdf"), &opts);
+    assert!(!output.contains("extern crate asdf;"));
+    assert!(!output.contains("__doctest_0"));
+    assert!(output.contains("#[path = \"doctest_1/lib.rs\"]\nmod __doctest_1;\n"));
+    assert!(output.contains("\"1\" => __doctest_1::__main(),\n"));
+}
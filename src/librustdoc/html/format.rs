@@ -15,7 +15,7 @@ use rustc_span::def_id::DefId;
 use rustc_target::spec::abi::Abi;
 
 use crate::clean::{self, PrimitiveType};
-use crate::formats::cache::cache;
+use crate::formats::cache::{cache, Cache};
 use crate::formats::item_type::ItemType;
 use crate::html::escape::Escape;
 use crate::html::render::cache::ExternalLocation;
@@ -192,15 +192,25 @@ impl clean::GenericParamDef {
 
                 Ok(())
             }
-            clean::GenericParamDefKind::Const { ref ty, .. } => {
+            clean::GenericParamDefKind::Const { ref ty, ref default, .. } => {
                 f.write_str("const ")?;
                 f.write_str(&self.name)?;
 
                 if f.alternate() {
-                    write!(f, ": {:#}", ty.print())
+                    write!(f, ": {:#}", ty.print())?;
                 } else {
-                    write!(f, ":&nbsp;{}", ty.print())
+                    write!(f, ":&nbsp;{}", ty.print())?;
                 }
+
+                if let Some(default) = default {
+                    if f.alternate() {
+                        write!(f, " = {}", default)?;
+                    } else {
+                        write!(f, "&nbsp;=&nbsp;{}", Escape(default))?;
+                    }
+                }
+
+                Ok(())
             }
         })
     }
@@ -481,14 +491,23 @@ impl clean::Path {
 }
 
 pub fn href(did: DefId) -> Option<(String, ItemType, Vec<String>)> {
-    let cache = cache();
+    let depth = CURRENT_DEPTH.with(|l| l.get());
+    href_with_root_path(did, &cache(), &"../".repeat(depth))
+}
+
+/// Like `href`, but with the cache and the relative path to the root of the documentation
+/// given explicitly, for pages that are rendered before the cache is put into TLS.
+crate fn href_with_root_path(
+    did: DefId,
+    cache: &Cache,
+    root_path: &str,
+) -> Option<(String, ItemType, Vec<String>)> {
     if !did.is_local() && !cache.access_levels.is_public(did) && !cache.document_private {
         return None;
     }
 
-    let depth = CURRENT_DEPTH.with(|l| l.get());
     let (fqp, shortty, mut url) = match cache.paths.get(&did) {
-        Some(&(ref fqp, shortty)) => (fqp, shortty, "../".repeat(depth)),
+        Some(&(ref fqp, shortty)) => (fqp, shortty, root_path.to_string()),
         None => {
             let &(ref fqp, shortty) = cache.external_paths.get(&did)?;
             (
@@ -496,7 +515,7 @@ pub fn href(did: DefId) -> Option<(String, ItemType, Vec<String>)> {
                 shortty,
                 match cache.extern_locations[&did.krate] {
                     (.., ExternalLocation::Remote(ref s)) => s.to_string(),
-                    (.., ExternalLocation::Local) => "../".repeat(depth),
+                    (.., ExternalLocation::Local) => root_path.to_string(),
                     (.., ExternalLocation::Unknown) => return None,
                 },
             )
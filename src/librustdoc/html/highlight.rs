@@ -9,6 +9,7 @@ use crate::html::escape::Escape;
 
 use std::fmt::{Display, Write};
 use std::iter::Peekable;
+use std::ops::Range;
 
 use rustc_lexer::{LiteralKind, TokenKind};
 use rustc_span::symbol::Ident;
@@ -34,21 +35,41 @@ pub fn render_with_highlighting(
     }
 
     write_header(&mut out, class);
-    write_code(&mut out, &src);
+    write_code(&mut out, &src, None);
     write_footer(&mut out, playground_button);
 
     out
 }
 
+/// Highlights `src` like `render_with_highlighting`, and turns every token that `link` returns a
+/// URL for into a link. `link` is given the byte range of the token in `src`.
+crate fn render_with_links(src: String, link: &dyn Fn(Range<u32>) -> Option<String>) -> String {
+    debug!("highlighting: ================\n{}\n==============", src);
+    let mut out = String::with_capacity(src.len());
+
+    write_header(&mut out, None);
+    write_code(&mut out, &src, Some(link));
+    write_footer(&mut out, None);
+
+    out
+}
+
 fn write_header(out: &mut String, class: Option<&str>) {
     write!(out, "<div class=\"example-wrap\"><pre class=\"rust {}\">\n", class.unwrap_or_default())
         .unwrap()
 }
 
-fn write_code(out: &mut String, src: &str) {
+fn write_code(out: &mut String, src: &str, link: Option<&dyn Fn(Range<u32>) -> Option<String>>) {
+    // The text of the tokens adds up to `src`, so this is the position of the next token.
+    let mut pos = 0;
     Classifier::new(src).highlight(&mut |highlight| {
         match highlight {
-            Highlight::Token { text, class } => string(out, Escape(text), class),
+            Highlight::Token { text, class } => {
+                let range = pos..pos + text.len() as u32;
+                pos = range.end;
+                let href = link.and_then(|link| link(range));
+                string(out, Escape(text), class, href.as_deref())
+            }
             Highlight::EnterSpan { class } => enter_span(out, class),
             Highlight::ExitSpan => exit_span(out),
         };
@@ -337,11 +358,19 @@ fn exit_span(out: &mut String) {
 /// ```
 /// The latter can be thought of as a shorthand for the former, which is more
 /// flexible.
-fn string<T: Display>(out: &mut String, text: T, klass: Class) {
+///
+/// If `href` is given, the text is also turned into a link to it.
+fn string<T: Display>(out: &mut String, text: T, klass: Class, href: Option<&str>) {
+    if let Some(href) = href {
+        write!(out, "<a href=\"{}\">", href).unwrap();
+    }
     match klass {
         Class::None => write!(out, "{}", text).unwrap(),
         klass => write!(out, "<span class=\"{}\">{}</span>", klass.as_html(), text).unwrap(),
     }
+    if href.is_some() {
+        write!(out, "</a>").unwrap();
+    }
 }
 
 #[cfg(test)]
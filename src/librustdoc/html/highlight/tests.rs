@@ -1,17 +1,31 @@
 use super::write_code;
 use expect_test::expect_file;
+use std::ops::Range;
 
 #[test]
 fn test_html_highlighting() {
     let src = include_str!("fixtures/sample.rs");
     let html = {
         let mut out = String::new();
-        write_code(&mut out, src);
+        write_code(&mut out, src, None);
         format!("{}<pre><code>{}</code></pre>\n", STYLE, out)
     };
     expect_file!["fixtures/sample.html"].assert_eq(&html);
 }
 
+#[test]
+fn test_links() {
+    let src = "fn foo() {}\nfoo();";
+    let mut out = String::new();
+    let link = |range: Range<u32>| if range == (12..15) { Some("#1".to_string()) } else { None };
+    write_code(&mut out, src, Some(&link));
+    assert_eq!(
+        out,
+        "<span class=\"kw\">fn</span> <span class=\"ident\">foo</span>() {}\n\
+         <a href=\"#1\"><span class=\"ident\">foo</span></a>();"
+    );
+}
+
 const STYLE: &str = r#"
 <style>
 .kw { color: #8959A8; }
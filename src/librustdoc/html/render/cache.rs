@@ -2,6 +2,7 @@ use std::collections::BTreeMap;
 use std::path::Path;
 
 use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
 use rustc_span::symbol::sym;
 use serde::Serialize;
 
@@ -61,12 +62,40 @@ pub fn extern_location(
         .unwrap_or(Unknown) // Well, at least we tried.
 }
 
-/// Builds the search index from the collected metadata
-pub fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
-    let mut defid_to_pathid = FxHashMap::default();
-    let mut crate_items = Vec::with_capacity(cache.search_index.len());
-    let mut crate_paths = vec![];
+/// The search index of a crate, split into shards by the first character of the names of the
+/// items so that the search only loads the shards it needs.
+crate struct SearchIndex {
+    /// The entry of the crate in `search-index.js`, with its summary and the keys of its shards.
+    crate crate_entry: String,
+    /// The contents of the files of the shards, by key.
+    crate shards: BTreeMap<char, String>,
+}
+
+/// Returns the key of the shard of the search index an item named `name` is in, which is the
+/// first character of the name if it's an ASCII letter or digit, lowercased, and `_` otherwise.
+///
+/// This must be kept in sync with `searchShardKey` in `main.js`.
+fn search_shard_key(name: &str) -> char {
+    name.chars()
+        .next()
+        .map(|c| c.to_ascii_lowercase())
+        .filter(|c| c.is_ascii_alphanumeric())
+        .unwrap_or('_')
+}
 
+/// Serializes `data` to JSON that can be put in a JS string between single quotes.
+fn to_js_json_string<T: Serialize>(data: &T) -> String {
+    serde_json::to_string(data)
+        .expect("failed serde conversion")
+        // All these `replace` calls are because we have to go through JS string for JSON content.
+        .replace(r"\", r"\\")
+        .replace("'", r"\'")
+        // We need to escape double quotes for the JSON.
+        .replace("\\\"", "\\\\\"")
+}
+
+/// Builds the search index from the collected metadata
+crate fn build_index(krate: &clean::Crate, cache: &mut Cache) -> SearchIndex {
     let Cache { ref mut search_index, ref orphan_impl_items, ref paths, ref mut aliases, .. } =
         *cache;
 
@@ -92,36 +121,63 @@ pub fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
         }
     }
 
-    // Reduce `DefId` in paths into smaller sequential numbers,
-    // and prune the paths that do not appear in the index.
-    let mut lastpath = String::new();
-    let mut lastpathid = 0usize;
+    #[derive(Default, Serialize)]
+    struct ShardData<'a> {
+        #[serde(rename = "i")]
+        items: Vec<&'a IndexItem>,
+        #[serde(rename = "p")]
+        paths: Vec<(ItemType, String)>,
+        // The String is alias name and the vec is the list of the elements with this alias.
+        //
+        // To be noted: the `usize` elements are indexes to `items`.
+        #[serde(rename = "a")]
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        aliases: BTreeMap<String, Vec<usize>>,
+        #[serde(skip)]
+        defid_to_pathid: FxHashMap<DefId, usize>,
+        #[serde(skip)]
+        lastpath: String,
+    }
+
+    let mut shards: BTreeMap<char, ShardData<'_>> = BTreeMap::new();
+    // The shard and the index in the shard of each item of `search_index`.
+    let mut item_positions = Vec::with_capacity(search_index.len());
 
+    // Every shard has its own list of paths, and omits the paths of its items on its own, so
+    // that it can be loaded without the other ones.
     for item in search_index {
+        let key = search_shard_key(&item.name);
+        let shard = shards.entry(key).or_default();
+
+        // Reduce `DefId` in paths into smaller sequential numbers,
+        // and prune the paths that do not appear in the index.
         item.parent_idx = item.parent.and_then(|defid| {
-            if defid_to_pathid.contains_key(&defid) {
-                defid_to_pathid.get(&defid).copied()
+            if let Some(&pathid) = shard.defid_to_pathid.get(&defid) {
+                Some(pathid)
             } else {
-                let pathid = lastpathid;
-                defid_to_pathid.insert(defid, pathid);
-                lastpathid += 1;
-
-                if let Some(&(ref fqp, short)) = paths.get(&defid) {
-                    crate_paths.push((short, fqp.last().unwrap().clone()));
-                    Some(pathid)
-                } else {
-                    None
-                }
+                let &(ref fqp, short) = paths.get(&defid)?;
+                let pathid = shard.paths.len();
+                shard.defid_to_pathid.insert(defid, pathid);
+                shard.paths.push((short, fqp.last().unwrap().clone()));
+                Some(pathid)
             }
         });
 
         // Omit the parent path if it is same to that of the prior item.
-        if lastpath == item.path {
+        if shard.lastpath == item.path {
             item.path.clear();
         } else {
-            lastpath = item.path.clone();
+            shard.lastpath = item.path.clone();
+        }
+        item_positions.push((key, shard.items.len()));
+        shard.items.push(&*item);
+    }
+
+    for (alias, items) in aliases.iter() {
+        for &(key, index) in items.iter().map(|&item| &item_positions[item]) {
+            let shard = shards.get_mut(&key).unwrap();
+            shard.aliases.entry(alias.clone()).or_default().push(index);
         }
-        crate_items.push(&*item);
     }
 
     let crate_doc = krate
@@ -131,37 +187,30 @@ pub fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
         .unwrap_or(String::new());
 
     #[derive(Serialize)]
-    struct CrateData<'a> {
+    struct CrateData {
         doc: String,
-        #[serde(rename = "i")]
-        items: Vec<&'a IndexItem>,
-        #[serde(rename = "p")]
-        paths: Vec<(ItemType, String)>,
-        // The String is alias name and the vec is the list of the elements with this alias.
-        //
-        // To be noted: the `usize` elements are indexes to `items`.
-        #[serde(rename = "a")]
-        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-        aliases: &'a BTreeMap<String, Vec<usize>>,
+        #[serde(rename = "s")]
+        shards: Vec<char>,
     }
 
-    // Collect the index into a string
-    format!(
+    let crate_entry = format!(
         r#""{}":{}"#,
         krate.name,
-        serde_json::to_string(&CrateData {
-            doc: crate_doc,
-            items: crate_items,
-            paths: crate_paths,
-            aliases,
+        to_js_json_string(&CrateData { doc: crate_doc, shards: shards.keys().copied().collect() })
+    );
+    let shards = shards
+        .into_iter()
+        .map(|(key, shard)| {
+            let contents = format!(
+                "addSearchShard(\"{}\",\"{}\",JSON.parse('{}'));",
+                krate.name,
+                key,
+                to_js_json_string(&shard)
+            );
+            (key, contents)
         })
-        .expect("failed serde conversion")
-        // All these `replace` calls are because we have to go through JS string for JSON content.
-        .replace(r"\", r"\\")
-        .replace("'", r"\'")
-        // We need to escape double quotes for the JSON.
-        .replace("\\\"", "\\\\\"")
-    )
+        .collect();
+    SearchIndex { crate_entry, shards }
 }
 
 crate fn get_index_search_type(item: &clean::Item) -> Option<IndexItemFunctionType> {
@@ -26,6 +26,7 @@
 //! both occur before the crate is rendered.
 
 pub mod cache;
+pub mod span_map;
 
 #[cfg(test)]
 mod tests;
@@ -40,6 +41,7 @@ use std::fmt::{self, Write};
 use std::fs::{self, File};
 use std::io::prelude::*;
 use std::io::{self, BufReader};
+use std::ops::Range;
 use std::path::{Component, Path, PathBuf};
 use std::rc::Rc;
 use std::str;
@@ -80,7 +82,9 @@ use crate::html::format::{print_abi_with_space, Buffer, PrintWithSpace};
 use crate::html::markdown::{self, ErrorCodes, IdMap, Markdown, MarkdownHtml, MarkdownSummaryLine};
 use crate::html::sources;
 use crate::html::{highlight, layout, static_files};
-use cache::{build_index, ExternalLocation};
+use crate::scrape_examples::{CallData, FnCallLocations};
+use cache::{build_index, ExternalLocation, SearchIndex};
+use span_map::SpanMap;
 
 /// A pair of name and its optional document.
 pub type NameDoc = (String, Option<String>);
@@ -157,6 +161,12 @@ crate struct SharedContext {
     pub edition: Edition,
     pub codes: ErrorCodes,
     playground: Option<markdown::Playground>,
+    /// The links from identifiers in the source pages to their definitions, if
+    /// `--generate-link-to-definition` was passed.
+    pub span_map: SpanMap,
+    /// The calls to the functions of the crate scraped from other crates, which are shown in
+    /// the documentation of these functions.
+    pub call_locations: FxHashMap<DefId, FnCallLocations>,
 }
 
 impl Context {
@@ -379,7 +389,7 @@ impl FormatRenderer for Context {
     fn init(
         mut krate: clean::Crate,
         options: RenderOptions,
-        _render_info: RenderInfo,
+        render_info: RenderInfo,
         edition: Edition,
         cache: &mut Cache,
     ) -> Result<(Context, clean::Crate), Error> {
@@ -466,6 +476,8 @@ impl FormatRenderer for Context {
             edition,
             codes: ErrorCodes::from(UnstableFeatures::from_environment().is_nightly_build()),
             playground,
+            span_map: render_info.span_map,
+            call_locations: render_info.call_locations,
         };
 
         // Add the default themes to the `Vec` of stylepaths
@@ -483,7 +495,7 @@ impl FormatRenderer for Context {
 
         let dst = output;
         scx.ensure_dir(&dst)?;
-        krate = sources::render(&dst, &mut scx, krate)?;
+        krate = sources::render(&dst, &mut scx, krate, cache)?;
 
         // Build our search index
         let index = build_index(&krate, cache);
@@ -677,7 +689,7 @@ impl FormatRenderer for Context {
 fn write_shared(
     cx: &Context,
     krate: &clean::Crate,
-    search_index: String,
+    search_index: SearchIndex,
     options: &RenderOptions,
     cache: &Cache,
 ) -> Result<(), Error> {
@@ -1023,7 +1035,7 @@ themePicker.onblur = handleThemeButtonsBlur;
     // Update the search index
     let dst = cx.dst.join(&format!("search-index{}.js", cx.shared.resource_suffix));
     let (mut all_indexes, mut krates) = try_err!(collect_json(&dst, &krate.name), &dst);
-    all_indexes.push(search_index);
+    all_indexes.push(search_index.crate_entry);
 
     // Sort the indexes by crate so the file will be generated identically even
     // with rustdoc running in parallel.
@@ -1036,6 +1048,15 @@ themePicker.onblur = handleThemeButtonsBlur;
         v.push_str("\\\n}');\naddSearchOptions(searchIndex);initSearch(searchIndex);");
         cx.shared.fs.write(&dst, &v)?;
     }
+
+    // The shards of the search index of the crate, which are loaded by the search when it needs
+    // them.
+    let shards_dir = cx.dst.join("search-index").join(&krate.name);
+    cx.shared.ensure_dir(&shards_dir)?;
+    for (key, shard) in &search_index.shards {
+        let dst = shards_dir.join(&format!("{}{}.js", key, cx.shared.resource_suffix));
+        cx.shared.fs.write(&dst, shard.as_bytes())?;
+    }
     if options.enable_index_page {
         if let Some(index_page) = options.index_page.clone() {
             let mut md_opts = options.clone();
@@ -2329,7 +2350,83 @@ fn item_function(w: &mut Buffer, cx: &Context, it: &clean::Item, f: &clean::Func
             .print(),
         spotlight = spotlight_decl(&f.decl),
     );
-    document(w, cx, it)
+    document(w, cx, it);
+    render_call_locations(w, cx, it);
+}
+
+/// Renders the calls to the function `item` that were scraped from other crates, if any. The
+/// first file with calls is shown, and the other ones are hidden behind a toggle.
+fn render_call_locations(w: &mut Buffer, cx: &Context, item: &clean::Item) {
+    let call_locations = match cx.shared.call_locations.get(&item.def_id) {
+        Some(call_locations) => call_locations,
+        None => return,
+    };
+    let mut examples = call_locations.values().filter_map(|data| render_scraped_example(cx, data));
+    let first = match examples.next() {
+        Some(first) => first,
+        None => return,
+    };
+
+    write!(
+        w,
+        "<details class='scraped-examples' open>\
+            <summary>Examples found in repository</summary>{}",
+        first
+    );
+    let others = examples.collect::<Vec<_>>();
+    if !others.is_empty() {
+        write!(
+            w,
+            "<details class='more-scraped-examples'><summary>More examples</summary>{}</details>",
+            others.join("")
+        );
+    }
+    write!(w, "</details>");
+}
+
+/// Renders the code of the items with calls in a file, where the calls link to their location in
+/// the source page of the file. Returns `None` if the file can't be read anymore.
+fn render_scraped_example(cx: &Context, call_data: &CallData) -> Option<String> {
+    let mut contents = fs::read_to_string(&call_data.path).ok()?;
+    // The positions of the calls were computed in the file as the compiler loaded it, without
+    // the BOM and with its newlines normalized.
+    if contents.starts_with("\u{feff}") {
+        contents.drain(..3);
+    }
+    let contents = contents.replace("\r\n", "\n");
+
+    let locations = &call_data.locations;
+    let byte_lo = locations.iter().map(|loc| loc.enclosing_item.byte_span.0).min()?;
+    let byte_hi = locations.iter().map(|loc| loc.enclosing_item.byte_span.1).max()?;
+    let line_lo = locations.iter().map(|loc| loc.enclosing_item.line_span.0).min()?;
+    let line_hi = locations.iter().map(|loc| loc.enclosing_item.line_span.1).max()?;
+    // Start at the beginning of the line, so that the first line is indented like the other ones.
+    let byte_lo = contents.get(..byte_lo as usize)?.rfind('\n').map_or(0, |i| i + 1);
+    let src = contents.get(byte_lo..byte_hi as usize)?;
+
+    let root_path = cx.root_path();
+    let link = |range: Range<u32>| {
+        let lo = range.start + byte_lo as u32;
+        let hi = range.end + byte_lo as u32;
+        let loc = locations
+            .iter()
+            .find(|loc| loc.call_expr.byte_span.0 <= lo && hi <= loc.call_expr.byte_span.1)?;
+        let (call_lo, call_hi) = loc.call_expr.line_span;
+        Some(format!("{}{}#{}-{}", root_path, call_data.url, call_lo, call_hi))
+    };
+
+    Some(format!(
+        "<div class='scraped-example'>\
+            <div class='scraped-example-title'><a href='{root}{url}#{lo}-{hi}'>{name}</a></div>\
+            {code}\
+         </div>",
+        root = root_path,
+        url = call_data.url,
+        lo = line_lo,
+        hi = line_hi,
+        name = Escape(&call_data.display_name),
+        code = highlight::render_with_links(src.to_string(), &link),
+    ))
 }
 
 fn render_implementor(
@@ -3697,6 +3794,7 @@ fn render_impl(
                     document_stability(w, cx, item, is_hidden);
                     if show_def_docs {
                         document_full(w, item, cx, "", is_hidden);
+                        render_call_locations(w, cx, item);
                     }
                 }
             } else {
@@ -0,0 +1,131 @@
+//! Collects the links from the identifiers in the local source files to their definitions,
+//! which are rendered on the source pages when `--generate-link-to-definition` is passed.
+//!
+//! Only paths that are resolved without type checking are linked: a path whose last segment is
+//! a method, or an associated item of a type, does not get a link.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir as hir;
+use rustc_hir::def::Res;
+use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc_hir::HirId;
+use rustc_middle::hir::map::Map;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::{FileName, Span};
+
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// What an identifier in a local source file links to.
+#[derive(Clone, Debug)]
+pub enum LinkFromSrc {
+    /// A definition in a local source file, given by its path and the line of the definition.
+    Local(PathBuf, usize),
+    /// An item of another crate, which links to its documentation page.
+    External(DefId),
+}
+
+/// The links of the identifiers of each local source file, by the byte range of the identifier
+/// in the file.
+pub type SpanMap = FxHashMap<PathBuf, FxHashMap<Range<u32>, LinkFromSrc>>;
+
+/// Collects the links of the identifiers in all the source files of the local crate.
+crate fn collect_spans(tcx: TyCtxt<'_>) -> SpanMap {
+    let mut visitor = SpanMapVisitor { tcx, links: SpanMap::default() };
+    intravisit::walk_crate(&mut visitor, tcx.hir().krate());
+    visitor.links
+}
+
+struct SpanMapVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    links: SpanMap,
+}
+
+impl SpanMapVisitor<'tcx> {
+    /// Returns the path of the local source file `span` is in, and the range of `span` in that
+    /// file. Returns `None` for spans that come from macro expansions, since they do not
+    /// correspond to text in the file.
+    fn file_range(&self, span: Span) -> Option<(PathBuf, Range<u32>)> {
+        if span.is_dummy() || span.from_expansion() {
+            return None;
+        }
+        let file = self.tcx.sess.source_map().lookup_source_file(span.lo());
+        if file.is_imported() || !file.contains(span.hi()) {
+            return None;
+        }
+        let path = match file.name {
+            FileName::Real(ref name) => name.local_path().to_path_buf(),
+            _ => return None,
+        };
+        Some((path, (span.lo() - file.start_pos).0..(span.hi() - file.start_pos).0))
+    }
+
+    /// Returns the link to the local definition at `span`.
+    fn local_link(&self, span: Span) -> Option<LinkFromSrc> {
+        // Items generated by macros link to the invocation of the macro.
+        let span = span.source_callsite();
+        let (path, _) = self.file_range(span)?;
+        let line = self.tcx.sess.source_map().lookup_char_pos(span.lo()).line;
+        Some(LinkFromSrc::Local(path, line))
+    }
+
+    fn link_for_res(&self, res: Res) -> Option<LinkFromSrc> {
+        let def_id = match res {
+            Res::Def(_, def_id) => def_id,
+            Res::SelfTy(_, Some((impl_def_id, _))) => impl_def_id,
+            Res::SelfTy(Some(trait_def_id), None) => trait_def_id,
+            Res::Local(hir_id) => return self.local_link(self.tcx.hir().span(hir_id)),
+            _ => return None,
+        };
+        if def_id.is_local() {
+            self.local_link(self.tcx.def_span(def_id))
+        } else {
+            Some(LinkFromSrc::External(def_id))
+        }
+    }
+
+    fn add_link(&mut self, span: Span, link: LinkFromSrc) {
+        if let Some((path, range)) = self.file_range(span) {
+            self.links.entry(path).or_default().insert(range, link);
+        }
+    }
+}
+
+impl Visitor<'tcx> for SpanMapVisitor<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::All(self.tcx.hir())
+    }
+
+    fn visit_path(&mut self, path: &'tcx hir::Path<'tcx>, _: HirId) {
+        // The last segment is resolved to `path.res`, the other ones may have their own
+        // resolution, like the modules of the path.
+        if let Some((last, segments)) = path.segments.split_last() {
+            for segment in segments {
+                if let Some(link) = segment.res.and_then(|res| self.link_for_res(res)) {
+                    self.add_link(segment.ident.span, link);
+                }
+            }
+            if let Some(link) = self.link_for_res(path.res) {
+                self.add_link(last.ident.span, link);
+            }
+        }
+        intravisit::walk_path(self, path);
+    }
+
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        // The name of a module declared with `mod foo;` links to the file of the module.
+        if let hir::ItemKind::Mod(ref module) = item.kind {
+            let module_file = self.file_range(module.inner).map(|(path, _)| path);
+            let item_file = self.file_range(item.span).map(|(path, _)| path);
+            if module_file.is_some() && module_file != item_file {
+                if let Some(link) = self.local_link(module.inner) {
+                    self.add_link(item.ident.span, link);
+                }
+            }
+        }
+        intravisit::walk_item(self, item);
+    }
+}
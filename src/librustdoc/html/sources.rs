@@ -2,26 +2,34 @@ use crate::clean;
 use crate::docfs::PathError;
 use crate::error::Error;
 use crate::fold::DocFolder;
-use crate::html::format::Buffer;
+use crate::formats::cache::Cache;
+use crate::html::format::{href_with_root_path, Buffer};
 use crate::html::highlight;
 use crate::html::layout;
+use crate::html::render::span_map::LinkFromSrc;
 use crate::html::render::{SharedContext, BASIC_KEYWORDS};
 use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_span::source_map::FileName;
 use std::ffi::OsStr;
 use std::fs;
+use std::ops::Range;
 use std::path::{Component, Path, PathBuf};
 
 crate fn render(
     dst: &Path,
     scx: &mut SharedContext,
     krate: clean::Crate,
+    cache: &Cache,
 ) -> Result<clean::Crate, Error> {
     info!("emitting source files");
     let dst = dst.join("src").join(&krate.name);
     scx.ensure_dir(&dst)?;
-    let mut folder = SourceCollector { dst, scx };
-    Ok(folder.fold_crate(krate))
+    let mut folder = SourceCollector { dst, scx, files: Vec::new() };
+    let krate = folder.fold_crate(krate);
+    // All the files are known before any of them is rendered, so that the links between them
+    // can be generated.
+    folder.emit_sources(&krate.name, cache);
+    Ok(krate)
 }
 
 /// Helper struct to render all source code to HTML pages
@@ -30,6 +38,9 @@ struct SourceCollector<'a> {
 
     /// Root destination to place all HTML output into
     dst: PathBuf,
+
+    /// The local source files to render, in the order they were found.
+    files: Vec<PathBuf>,
 }
 
 impl<'a> DocFolder for SourceCollector<'a> {
@@ -42,95 +53,140 @@ impl<'a> DocFolder for SourceCollector<'a> {
             // skip non-local files
             && item.source.cnum == LOCAL_CRATE
         {
-            // If it turns out that we couldn't read this file, then we probably
-            // can't read any of the files (generating html output from json or
-            // something like that), so just don't include sources for the
-            // entire crate. The other option is maintaining this mapping on a
-            // per-file basis, but that's probably not worth it...
-            self.scx.include_sources = match self.emit_source(&item.source.filename) {
-                Ok(()) => true,
-                Err(e) => {
-                    println!(
-                        "warning: source code was requested to be rendered, \
-                         but processing `{}` had an error: {}",
-                        item.source.filename, e
-                    );
-                    println!("         skipping rendering of source code");
-                    false
-                }
-            };
+            self.add_source(&item.source.filename);
         }
         self.fold_item_recur(item)
     }
 }
 
 impl<'a> SourceCollector<'a> {
-    /// Renders the given filename into its corresponding HTML source file.
-    fn emit_source(&mut self, filename: &FileName) -> Result<(), Error> {
+    /// Adds the given filename to the files to render, and records the url-path of its page.
+    fn add_source(&mut self, filename: &FileName) {
         let p = match *filename {
             FileName::Real(ref file) => file.local_path().to_path_buf(),
-            _ => return Ok(()),
+            _ => return,
         };
         if self.scx.local_sources.contains_key(&*p) {
-            // We've already emitted this source
-            return Ok(());
+            // We've already seen this source
+            return;
         }
 
-        let mut contents = match fs::read_to_string(&p) {
-            Ok(contents) => contents,
-            Err(e) => {
-                return Err(Error::new(e, &p));
+        let href = source_href(&self.scx.src_root, &p);
+        self.scx.local_sources.insert(p.clone(), href);
+        self.files.push(p);
+    }
+
+    /// Renders all the files that were found while folding the crate.
+    fn emit_sources(&mut self, krate_name: &str, cache: &Cache) {
+        for p in &self.files {
+            // If it turns out that we couldn't read this file, then we probably
+            // can't read any of the files (generating html output from json or
+            // something like that), so just don't include sources for the
+            // entire crate. The other option is maintaining this mapping on a
+            // per-file basis, but that's probably not worth it...
+            if let Err(e) = emit_source(&self.scx, &self.dst, p, krate_name, cache) {
+                println!(
+                    "warning: source code was requested to be rendered, \
+                     but processing `{}` had an error: {}",
+                    p.display(),
+                    e
+                );
+                println!("         skipping rendering of source code");
+                self.scx.include_sources = false;
+                return;
             }
-        };
+        }
+    }
+}
 
-        // Remove the utf-8 BOM if any
-        if contents.starts_with("\u{feff}") {
-            contents.drain(..3);
+/// Renders the given file into its corresponding HTML source file.
+fn emit_source(
+    scx: &SharedContext,
+    dst: &Path,
+    p: &Path,
+    krate_name: &str,
+    cache: &Cache,
+) -> Result<(), Error> {
+    let mut contents = match fs::read_to_string(p) {
+        Ok(contents) => contents,
+        Err(e) => {
+            return Err(Error::new(e, p));
         }
+    };
 
-        // Create the intermediate directories
-        let mut cur = self.dst.clone();
-        let mut root_path = String::from("../../");
-        let mut href = String::new();
-        clean_path(&self.scx.src_root, &p, false, |component| {
-            cur.push(component);
-            root_path.push_str("../");
-            href.push_str(&component.to_string_lossy());
-            href.push('/');
-        });
-        self.scx.ensure_dir(&cur)?;
-        let mut fname = p.file_name().expect("source has no filename").to_os_string();
-        fname.push(".html");
-        cur.push(&fname);
-        href.push_str(&fname.to_string_lossy());
-
-        let title = format!(
-            "{} -- source",
-            cur.file_name().expect("failed to get file name").to_string_lossy()
-        );
-        let desc = format!("Source to the Rust file `{}`.", filename);
-        let page = layout::Page {
-            title: &title,
-            css_class: "source",
-            root_path: &root_path,
-            static_root_path: self.scx.static_root_path.as_deref(),
-            description: &desc,
-            keywords: BASIC_KEYWORDS,
-            resource_suffix: &self.scx.resource_suffix,
-            extra_scripts: &[&format!("source-files{}", self.scx.resource_suffix)],
-            static_extra_scripts: &[&format!("source-script{}", self.scx.resource_suffix)],
-        };
-        let v = layout::render(
-            &self.scx.layout,
-            &page,
-            "",
-            |buf: &mut _| print_src(buf, contents),
-            &self.scx.style_files,
-        );
-        self.scx.fs.write(&cur, v.as_bytes())?;
-        self.scx.local_sources.insert(p, href);
-        Ok(())
+    // Remove the utf-8 BOM if any
+    if contents.starts_with("\u{feff}") {
+        contents.drain(..3);
+    }
+
+    let links = scx.span_map.get(p);
+    if links.is_some() {
+        // The links are keyed by positions in the file as the compiler loaded it, which has
+        // its newlines normalized.
+        contents = contents.replace("\r\n", "\n");
     }
+
+    // Create the intermediate directories
+    let mut cur = dst.to_path_buf();
+    let mut root_path = String::from("../../");
+    clean_path(&scx.src_root, p, false, |component| {
+        cur.push(component);
+        root_path.push_str("../");
+    });
+    scx.ensure_dir(&cur)?;
+    let mut fname = p.file_name().expect("source has no filename").to_os_string();
+    fname.push(".html");
+    cur.push(&fname);
+
+    let link = |range: Range<u32>| match links?.get(&range)? {
+        LinkFromSrc::Local(path, line) => {
+            let href = scx.local_sources.get(path)?;
+            Some(format!("{}src/{}/{}#{}", root_path, krate_name, href, line))
+        }
+        LinkFromSrc::External(def_id) => {
+            href_with_root_path(*def_id, cache, &root_path).map(|(url, ..)| url)
+        }
+    };
+
+    let title = format!(
+        "{} -- source",
+        cur.file_name().expect("failed to get file name").to_string_lossy()
+    );
+    let desc = format!("Source to the Rust file `{}`.", p.display());
+    let page = layout::Page {
+        title: &title,
+        css_class: "source",
+        root_path: &root_path,
+        static_root_path: scx.static_root_path.as_deref(),
+        description: &desc,
+        keywords: BASIC_KEYWORDS,
+        resource_suffix: &scx.resource_suffix,
+        extra_scripts: &[&format!("source-files{}", scx.resource_suffix)],
+        static_extra_scripts: &[&format!("source-script{}", scx.resource_suffix)],
+    };
+    let v = layout::render(
+        &scx.layout,
+        &page,
+        "",
+        |buf: &mut _| print_src(buf, contents, &link),
+        &scx.style_files,
+    );
+    scx.fs.write(&cur, v.as_bytes())?;
+    Ok(())
+}
+
+/// Returns the url-path of the source page of the file at `p`, relative to the directory of the
+/// source pages of its crate.
+crate fn source_href(src_root: &Path, p: &Path) -> String {
+    let mut href = String::new();
+    clean_path(src_root, p, false, |component| {
+        href.push_str(&component.to_string_lossy());
+        href.push('/');
+    });
+    let mut fname = p.file_name().expect("source has no filename").to_os_string();
+    fname.push(".html");
+    href.push_str(&fname.to_string_lossy());
+    href
 }
 
 /// Takes a path to a source file and cleans the path to it. This canonicalizes
@@ -162,7 +218,7 @@ where
 
 /// Wrapper struct to render the source code of a file. This will do things like
 /// adding line numbers to the left-hand side.
-fn print_src(buf: &mut Buffer, s: String) {
+fn print_src(buf: &mut Buffer, s: String, link: &dyn Fn(Range<u32>) -> Option<String>) {
     let lines = s.lines().count();
     let mut cols = 0;
     let mut tmp = lines;
@@ -175,5 +231,5 @@ fn print_src(buf: &mut Buffer, s: String) {
         write!(buf, "<span id=\"{0}\">{0:1$}</span>\n", i, cols);
     }
     write!(buf, "</pre>");
-    write!(buf, "{}", highlight::render_with_highlighting(s, None, None, None));
+    write!(buf, "{}", highlight::render_with_links(s, link));
 }
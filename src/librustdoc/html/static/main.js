@@ -3,7 +3,7 @@
 
 // Local js definitions:
 /* global addClass, getCurrentValue, hasClass */
-/* global onEachLazy, hasOwnProperty, removeClass, updateLocalStorage */
+/* global onEachLazy, hasOwnProperty, removeClass, resourcesSuffix, updateLocalStorage */
 
 if (!String.prototype.startsWith) {
     String.prototype.startsWith = function(searchString, position) {
@@ -568,6 +568,8 @@ function defocusSearchBar() {
         var NO_TYPE_FILTER = -1;
         var currentResults, index, searchIndex;
         var ALIASES = {};
+        // The number of shards of the search index being loaded.
+        var searchShardsLoading = 0;
         var params = getQueryStringParams();
 
         // Populate search bar with query string search term when provided,
@@ -1577,9 +1579,12 @@ function defocusSearchBar() {
             return "<div>" + text + " <div class=\"count\">(" + nbElems + ")</div></div>";
         }
 
-        function showResults(results) {
+        function showResults(results, partial) {
             var search = getSearchElement();
-            if (results.others.length === 1
+            // The only result of a partial search may not be the only one once the rest of the
+            // search index is loaded, and the user may want to load it.
+            if (partial !== true
+                && results.others.length === 1
                 && getCurrentValue("rustdoc-go-to-only-result") === "true"
                 // By default, the search DOM element is "empty" (meaning it has no children not
                 // text content). Once a search has been run, it won't be empty, even if you press
@@ -1604,6 +1609,9 @@ function defocusSearchBar() {
 
             var output = "<h1>Results for " + escape(query.query) +
                 (query.type ? " (type: " + escape(query.type) + ")" : "") + "</h1>" +
+                (partial === true ?
+                    "<p id=\"search-all\">Not all the items were searched. " +
+                    "<a href=\"#\">Search all the items</a></p>" : "") +
                 "<div id=\"titles\">" +
                 makeTabHeader(0, "In Names", ret_others[1]) +
                 makeTabHeader(1, "In Parameters", ret_in_args[1]) +
@@ -1612,6 +1620,14 @@ function defocusSearchBar() {
                 ret_others[0] + ret_in_args[0] + ret_returned[0] + "</div>";
 
             search.innerHTML = output;
+            var searchAll = document.getElementById("search-all");
+            if (searchAll !== null) {
+                searchAll.getElementsByTagName("a")[0].onclick = function(e) {
+                    e.preventDefault();
+                    searchAll.textContent = "Loading all the items...";
+                    loadAllSearchShards(getFilterCrates());
+                };
+            }
             showSearchResults(search);
             var tds = search.getElementsByTagName("td");
             var td_width = 0;
@@ -1711,6 +1727,134 @@ function defocusSearchBar() {
             return undefined;
         }
 
+        /**
+         * Returns the key of the shard of the search index containing the items named `name`,
+         * which is its first character if it's an ASCII letter or digit, lowercased, and `_`
+         * otherwise.
+         *
+         * This must be kept in sync with `search_shard_key` in `html/render/cache.rs`.
+         */
+        function searchShardKey(name) {
+            var c = name.charAt(0).toLowerCase();
+            return /^[a-z0-9]$/.test(c) ? c : "_";
+        }
+
+        /**
+         * Returns the key of the shard of the search index containing the items named like the
+         * name searched by `query`, or `null` if `query` doesn't search for a name, like the
+         * searches by type.
+         */
+        function queryShardKey(query) {
+            var name = query.query.trim().replace(/^(["'])(.*)\1$/, "$2").split("::").pop();
+            if (!/^\w+$/.test(name)) {
+                return null;
+            }
+            return searchShardKey(name);
+        }
+
+        /**
+         * Loads the shard of the search index with the given key of `crate`. The search is run
+         * again once all the shards being loaded are.
+         */
+        function requestSearchShard(crate, key) {
+            var shards = rawSearchIndex[crate].shards;
+            var script = document.createElement("script");
+            script.src = rootPath + "search-index/" + crate + "/" + key + resourcesSuffix + ".js";
+            script.onload = script.onerror = function() {
+                // A shard that couldn't be loaded isn't requested again.
+                if (shards[key] === undefined) {
+                    shards[key] = null;
+                }
+                searchShardsLoading -= 1;
+                if (searchShardsLoading === 0) {
+                    index = buildIndex(rawSearchIndex);
+                    currentResults = null;
+                    if (!hasClass(getSearchElement(), "hidden")) {
+                        search(undefined, true);
+                    }
+                }
+            };
+            shards[key] = undefined;
+            searchShardsLoading += 1;
+            document.head.appendChild(script);
+        }
+
+        /**
+         * Starts loading the shards of the search index needed to search for `query` in
+         * `filterCrates` that aren't loaded yet. Returns `true` if the search has to wait for
+         * them to be loaded.
+         *
+         * When searching for a name, only the shards of the items starting like it are loaded.
+         * The other shards, which are needed to find the items whose name only contains the
+         * searched one or is close to it, and to search by type, are only loaded when asked for
+         * with `loadAllSearchShards`.
+         */
+        function loadSearchShards(query, filterCrates) {
+            var queryKey = queryShardKey(query);
+            var waiting = false;
+
+            if (queryKey === null) {
+                return false;
+            }
+            for (var crate in rawSearchIndex) {
+                if (!hasOwnProperty(rawSearchIndex, crate) ||
+                    (filterCrates !== undefined && crate !== filterCrates)) {
+                    continue;
+                }
+                var keys = rawSearchIndex[crate].s || [];
+                var shards = rawSearchIndex[crate].shards;
+                if (keys.indexOf(queryKey) === -1) {
+                    continue;
+                }
+                if (!hasOwnProperty(shards, queryKey)) {
+                    requestSearchShard(crate, queryKey);
+                }
+                if (shards[queryKey] === undefined) {
+                    waiting = true;
+                }
+            }
+            return waiting;
+        }
+
+        /**
+         * Calls `f` with each crate of `filterCrates` and the key of each of its shards that
+         * hasn't been requested yet.
+         */
+        function forEachMissingSearchShard(filterCrates, f) {
+            for (var crate in rawSearchIndex) {
+                if (!hasOwnProperty(rawSearchIndex, crate) ||
+                    (filterCrates !== undefined && crate !== filterCrates)) {
+                    continue;
+                }
+                var keys = rawSearchIndex[crate].s || [];
+                for (var i = 0; i < keys.length; ++i) {
+                    if (!hasOwnProperty(rawSearchIndex[crate].shards, keys[i])) {
+                        f(crate, keys[i]);
+                    }
+                }
+            }
+        }
+
+        /**
+         * Returns `true` if some shards of the search index of `filterCrates` haven't been
+         * requested, so the results of a search may be missing some items.
+         */
+        function hasMissingSearchShards(filterCrates) {
+            var missing = false;
+            forEachMissingSearchShard(filterCrates, function() {
+                missing = true;
+            });
+            return missing;
+        }
+
+        /**
+         * Starts loading all the shards of the search index of `filterCrates` that aren't
+         * loaded yet. The search is run again once they are.
+         */
+        function loadAllSearchShards(filterCrates) {
+            forEachMissingSearchShard(filterCrates, requestSearchShard);
+        }
+
         function search(e, forced) {
             var params = getQueryStringParams();
             var query = getQuery(search_input.value.trim());
@@ -1743,7 +1887,52 @@ function defocusSearchBar() {
             }
 
             var filterCrates = getFilterCrates();
-            showResults(execSearch(query, index, filterCrates));
+            if (loadSearchShards(query, filterCrates)) {
+                return;
+            }
+            showResults(execSearch(query, index, filterCrates),
+                        hasMissingSearchShards(filterCrates));
+        }
+
+        /**
+         * Merges the loaded shards of the search index of a crate into the layout of the index
+         * of a crate that isn't sharded.
+         */
+        function mergeSearchShards(crateIndex) {
+            if (!crateIndex.s) {
+                return crateIndex;
+            }
+            var merged = {doc: crateIndex.doc, i: [], p: [], a: {}};
+            for (var i = 0; i < crateIndex.s.length; ++i) {
+                var shard = crateIndex.shards[crateIndex.s[i]];
+                if (!shard) {
+                    continue;
+                }
+                var itemOffset = merged.i.length;
+                var pathOffset = merged.p.length;
+                var j;
+
+                // The first item of a shard always has its path, so the items can be appended.
+                for (j = 0; j < shard.i.length; ++j) {
+                    var item = shard.i[j].slice();
+                    if (typeof item[4] === "number") {
+                        item[4] += pathOffset;
+                    }
+                    merged.i.push(item);
+                }
+                merged.p = merged.p.concat(shard.p);
+                for (var alias_name in shard.a) {
+                    if (!shard.a.hasOwnProperty(alias_name)) { continue; }
+
+                    if (!merged.a.hasOwnProperty(alias_name)) {
+                        merged.a[alias_name] = [];
+                    }
+                    for (j = 0; j < shard.a[alias_name].length; ++j) {
+                        merged.a[alias_name].push(shard.a[alias_name][j] + itemOffset);
+                    }
+                }
+            }
+            return merged;
         }
 
         function buildIndex(rawSearchIndex) {
@@ -1774,18 +1963,20 @@ function defocusSearchBar() {
                 //              (String) description,
                 //              (Number | null) the parent path index to `paths`]
                 //              (Object | null) the type of the function (if any)
-                var items = rawSearchIndex[crate].i;
+                var crateIndex = mergeSearchShards(rawSearchIndex[crate]);
+                var items = crateIndex.i;
                 // an array of [(Number) item type,
                 //              (String) name]
-                var paths = rawSearchIndex[crate].p;
+                var rawPaths = crateIndex.p;
                 // a array of [(String) alias name
                 //             [Number] index to items]
-                var aliases = rawSearchIndex[crate].a;
+                var aliases = crateIndex.a;
 
                 // convert `rawPaths` entries into object form
-                var len = paths.length;
+                var paths = [];
+                var len = rawPaths.length;
                 for (i = 0; i < len; ++i) {
-                    paths[i] = {ty: paths[i][0], name: paths[i][1]};
+                    paths.push({ty: rawPaths[i][0], name: rawPaths[i][1]});
                 }
 
                 // convert `items` into an object form, and construct word indices.
@@ -1921,6 +2112,14 @@ function defocusSearchBar() {
             search();
         }
 
+        Object.keys(rawSearchIndex).forEach(function(crate) {
+            rawSearchIndex[crate].shards = {};
+        });
+        // Called by the files of the shards of the search index once they are loaded.
+        window.addSearchShard = function(crate, key, shard) {
+            rawSearchIndex[crate].shards[key] = shard;
+        };
+
         index = buildIndex(rawSearchIndex);
         startSearch();
 
@@ -1,47 +1,243 @@
+//! Rustdoc's JSON backend
+//!
+//! This module contains the logic for rendering a crate as JSON rather than the normal static HTML
+//! output. See [the RFC](https://github.com/rust-lang/rfcs/pull/2963) and the [`types`] module
+//! docs for usage and details.
+
+mod conversions;
+pub mod types;
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
+use rustc_span::edition::Edition;
+
 use crate::clean;
 use crate::config::{RenderInfo, RenderOptions};
+use crate::docfs::PathError;
 use crate::error::Error;
 use crate::formats::cache::Cache;
 use crate::formats::FormatRenderer;
+use crate::html::render::cache::ExternalLocation;
 
-use rustc_span::edition::Edition;
+/// The version of the JSON format emitted by this backend. It is bumped on every backwards
+/// incompatible change to the types in [`types`].
+const FORMAT_VERSION: u32 = 1;
 
 #[derive(Clone)]
-pub struct JsonRenderer {}
+pub struct JsonRenderer {
+    /// A mapping of IDs that contains all local items for this crate which gets output as a top
+    /// level field of the JSON blob.
+    index: Rc<RefCell<FxHashMap<types::Id, types::Item>>>,
+    /// The directory where the blob will be written to.
+    out_path: PathBuf,
+}
+
+impl JsonRenderer {
+    fn get_trait_implementors(&mut self, id: DefId, cache: &Cache) -> Vec<types::Id> {
+        cache
+            .implementors
+            .get(&id)
+            .map(|implementors| {
+                implementors
+                    .iter()
+                    .map(|i| {
+                        let item = &i.impl_item;
+                        self.item(item.clone(), cache).unwrap();
+                        item.def_id.into()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn get_impls(&mut self, id: DefId, cache: &Cache) -> Vec<types::Id> {
+        cache
+            .impls
+            .get(&id)
+            .map(|impls| {
+                impls
+                    .iter()
+                    .filter_map(|i| {
+                        let item = &i.impl_item;
+                        if item.def_id.is_local() {
+                            self.item(item.clone(), cache).unwrap();
+                            Some(item.def_id.into())
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// External traits aren't part of the crate being documented, but their items are referenced
+    /// by local impls, so synthesize an index entry for every one of them that the cache knows.
+    fn get_trait_items(&mut self, cache: &Cache) -> Vec<(types::Id, types::Item)> {
+        cache
+            .traits
+            .iter()
+            .filter_map(|(&id, trait_item)| {
+                // only need to synthesize items for external traits
+                if !id.is_local() {
+                    trait_item.items.clone().into_iter().for_each(|i| self.item(i, cache).unwrap());
+                    let name = cache
+                        .paths
+                        .get(&id)
+                        .or_else(|| cache.external_paths.get(&id))
+                        .and_then(|(path, _)| path.last().cloned());
+                    Some((
+                        id.into(),
+                        types::Item {
+                            crate_id: id.krate.as_u32(),
+                            name,
+                            source: None,
+                            visibility: types::Visibility::Public,
+                            docs: String::new(),
+                            links: FxHashMap::default(),
+                            attrs: Vec::new(),
+                            stability: None,
+                            deprecation: None,
+                            kind: types::ItemKind::Trait,
+                            inner: types::ItemEnum::TraitItem(trait_item.clone().into()),
+                        },
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
 
 impl FormatRenderer for JsonRenderer {
     fn init(
-        _krate: clean::Crate,
-        _options: RenderOptions,
+        krate: clean::Crate,
+        options: RenderOptions,
         _render_info: RenderInfo,
         _edition: Edition,
         _cache: &mut Cache,
     ) -> Result<(Self, clean::Crate), Error> {
-        unimplemented!()
+        debug!("Initializing json renderer");
+        Ok((
+            JsonRenderer {
+                index: Rc::new(RefCell::new(FxHashMap::default())),
+                out_path: options.output,
+            },
+            krate,
+        ))
     }
 
-    fn item(&mut self, _item: clean::Item, _cache: &Cache) -> Result<(), Error> {
-        unimplemented!()
+    /// Inserts an item into the index. This should be used rather than directly calling insert on
+    /// the hashmap because certain items (traits and types) need to have their mappings for trait
+    /// implementations filled out before they're inserted.
+    fn item(&mut self, item: clean::Item, cache: &Cache) -> Result<(), Error> {
+        // Stripped items only survive the passes to keep the public items they contain reachable,
+        // they aren't part of the documented API themselves.
+        if item.is_stripped() {
+            return Ok(());
+        }
+
+        // Flatten items that recursively store other items
+        for inner in item.inner.inner_items() {
+            self.item(inner.clone(), cache)?;
+        }
+
+        let id = item.def_id;
+        let mut new_item: types::Item = item.into();
+        match new_item.inner {
+            types::ItemEnum::TraitItem(ref mut t) => {
+                t.implementors = self.get_trait_implementors(id, cache)
+            }
+            types::ItemEnum::StructItem(ref mut s) => s.impls = self.get_impls(id, cache),
+            types::ItemEnum::EnumItem(ref mut e) => e.impls = self.get_impls(id, cache),
+            _ => {}
+        }
+        self.index.borrow_mut().insert(id.into(), new_item);
+        Ok(())
     }
 
     fn mod_item_in(
         &mut self,
-        _item: &clean::Item,
-        _item_name: &str,
-        _cache: &Cache,
+        item: &clean::Item,
+        _module_name: &str,
+        cache: &Cache,
     ) -> Result<(), Error> {
-        unimplemented!()
+        if let clean::ModuleItem(ref m) = item.inner {
+            for item in &m.items {
+                // Impls and imports don't have names, so `run_format` never passes them to
+                // `item` on its own.
+                if item.name.is_none() {
+                    self.item(item.clone(), cache)?;
+                }
+            }
+        }
+        self.item(item.clone(), cache)
     }
 
     fn mod_item_out(&mut self, _item_name: &str) -> Result<(), Error> {
-        unimplemented!()
+        Ok(())
     }
 
-    fn after_krate(&mut self, _krate: &clean::Crate, _cache: &Cache) -> Result<(), Error> {
-        unimplemented!()
+    fn after_krate(&mut self, krate: &clean::Crate, cache: &Cache) -> Result<(), Error> {
+        debug!("Done with crate");
+        let trait_items = self.get_trait_items(cache);
+        let mut index = (*self.index).clone().into_inner();
+        index.extend(trait_items);
+        let output = types::Crate {
+            root: DefId { krate: LOCAL_CRATE, index: CRATE_DEF_INDEX }.into(),
+            version: krate.version.clone(),
+            includes_private: cache.document_private,
+            index,
+            paths: cache
+                .paths
+                .iter()
+                .chain(cache.external_paths.iter())
+                .map(|(&k, (path, kind))| {
+                    (
+                        k.into(),
+                        types::ItemSummary {
+                            crate_id: k.krate.as_u32(),
+                            path: path.clone(),
+                            kind: (*kind).into(),
+                        },
+                    )
+                })
+                .collect(),
+            external_crates: cache
+                .extern_locations
+                .iter()
+                .map(|(k, (name, _, location))| {
+                    (
+                        k.as_u32(),
+                        types::ExternalCrate {
+                            name: name.clone(),
+                            html_root_url: match location {
+                                ExternalLocation::Remote(s) => Some(s.clone()),
+                                _ => None,
+                            },
+                        },
+                    )
+                })
+                .collect(),
+            format_version: FORMAT_VERSION,
+        };
+        try_err!(std::fs::create_dir_all(&self.out_path), &self.out_path);
+        let mut p = self.out_path.clone();
+        p.push(&krate.name);
+        p.set_extension("json");
+        let file = BufWriter::new(try_err!(File::create(&p), &p));
+        try_err!(serde_json::ser::to_writer(file, &output), &p);
+        Ok(())
     }
 
     fn after_run(&mut self, _diag: &rustc_errors::Handler) -> Result<(), Error> {
-        unimplemented!()
+        Ok(())
     }
 }
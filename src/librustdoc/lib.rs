@@ -81,6 +81,7 @@ pub mod html;
 mod json;
 mod markdown;
 mod passes;
+mod scrape_examples;
 mod theme;
 mod visit_ast;
 mod visit_lib;
@@ -150,6 +151,7 @@ fn opts() -> Vec<RustcOptGroup> {
             o.optmulti("L", "library-path", "directory to add to crate search path", "DIR")
         }),
         stable("cfg", |o| o.optmulti("", "cfg", "pass a --cfg to rustc", "")),
+        unstable("check-cfg", |o| o.optmulti("", "check-cfg", "pass a --check-cfg to rustc", "")),
         stable("extern", |o| o.optmulti("", "extern", "pass an --extern to rustc", "NAME[=PATH]")),
         unstable("extern-html-root-url", |o| {
             o.optmulti("", "extern-html-root-url", "base URL to use for dependencies", "NAME=URL")
@@ -386,6 +388,46 @@ fn opts() -> Vec<RustcOptGroup> {
                 "parse ignore-foo for ignoring doctests on a per-target basis",
             )
         }),
+        unstable("generate-link-to-definition", |o| {
+            o.optflag(
+                "",
+                "generate-link-to-definition",
+                "Make the identifiers in the HTML source code pages navigable",
+            )
+        }),
+        unstable("merge-doctests", |o| {
+            o.optflag(
+                "",
+                "merge-doctests",
+                "compile the doctests that allow it into a single binary per edition",
+            )
+        }),
+        unstable("scrape-examples-output-path", |o| {
+            o.optopt(
+                "",
+                "scrape-examples-output-path",
+                "collect the calls to the functions of the target crates in the crate, \
+                 and write them to this file",
+                "PATH",
+            )
+        }),
+        unstable("scrape-examples-target-crate", |o| {
+            o.optmulti(
+                "",
+                "scrape-examples-target-crate",
+                "collect the calls to the functions of this crate",
+                "CRATE",
+            )
+        }),
+        unstable("with-examples", |o| {
+            o.optmulti(
+                "",
+                "with-examples",
+                "show the calls to the functions of the crate scraped in this file \
+                 in their documentation",
+                "PATH",
+            )
+        }),
         unstable("runtool", |o| {
             o.optopt(
                 "",
@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
+use rustc_ast::{LitKind, MetaItemKind};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_span::symbol::sym;
+
 use crate::clean::cfg::Cfg;
-use crate::clean::{Crate, Item};
+use crate::clean::{Attributes, AttributesExt, Crate, Item};
 use crate::core::DocContext;
 use crate::fold::DocFolder;
 use crate::passes::Pass;
@@ -9,21 +13,96 @@ use crate::passes::Pass;
 pub const PROPAGATE_DOC_CFG: Pass = Pass {
     name: "propagate-doc-cfg",
     run: propagate_doc_cfg,
-    description: "propagates `#[doc(cfg(...))]` to child items",
+    description: "infers `#[doc(cfg(...))]` from `#[cfg(...)]` and propagates it to child items",
 };
 
-pub fn propagate_doc_cfg(cr: Crate, _: &DocContext<'_>) -> Crate {
-    CfgPropagator { parent_cfg: None }.fold_crate(cr)
+pub fn propagate_doc_cfg(cr: Crate, cx: &DocContext<'_>) -> Crate {
+    let hidden_cfg = hidden_cfg(&cr, cx);
+    CfgPropagator { parent_cfg: None, hidden_cfg }.fold_crate(cr)
+}
+
+/// Returns the configuration options to leave out of the `doc(cfg)` inferred from the `#[cfg]`
+/// attributes of the items, or `None` if it isn't inferred in this crate.
+///
+/// It is inferred in crates with `#![feature(doc_cfg)]`, unless they opt out with
+/// `#![doc(auto_cfg = false)]`. The `doc`, `doctest` and `test` options are always left out, and
+/// more can be given with `#![doc(cfg_hide(...))]`.
+fn hidden_cfg(cr: &Crate, cx: &DocContext<'_>) -> Option<FxHashSet<Cfg>> {
+    if !cx.tcx.features().doc_cfg {
+        return None;
+    }
+
+    let mut hidden_cfg: FxHashSet<Cfg> =
+        [sym::doc, sym::doctest, sym::test].iter().map(|&name| Cfg::Cfg(name, None)).collect();
+    for attr in cr.module.as_ref().unwrap().attrs.lists(sym::doc) {
+        if attr.has_name(sym::auto_cfg) {
+            if let Some(MetaItemKind::NameValue(lit)) = attr.meta_item().map(|mi| &mi.kind) {
+                if let LitKind::Bool(false) = lit.kind {
+                    return None;
+                }
+            }
+        } else if attr.has_name(sym::cfg_hide) {
+            let cfg_mis = attr.meta_item_list().unwrap_or(&[]).iter().filter_map(|i| i.meta_item());
+            for cfg_mi in cfg_mis {
+                match Cfg::parse(cfg_mi) {
+                    Ok(cfg) => {
+                        hidden_cfg.insert(cfg);
+                    }
+                    Err(e) => cx.sess().diagnostic().span_err(e.span, e.msg),
+                }
+            }
+        }
+    }
+    Some(hidden_cfg)
+}
+
+/// Infers the `doc(cfg)` of an item from its `#[cfg]` attributes, if it doesn't have an explicit
+/// `#[doc(cfg(...))]`.
+fn auto_cfg(attrs: &Attributes, hidden_cfg: &FxHashSet<Cfg>) -> Option<Cfg> {
+    let has_doc_cfg = attrs.other_attrs.iter().any(|attr| {
+        attr.has_name(sym::doc)
+            && attr.meta().map_or(false, |mi| Attributes::extract_cfg(&mi).is_some())
+    });
+    if has_doc_cfg {
+        return None;
+    }
+
+    let cfg = attrs
+        .other_attrs
+        .iter()
+        .filter(|attr| attr.has_name(sym::cfg))
+        .filter_map(|attr| match attr.meta_item_list()?.as_slice() {
+            [nested] => Cfg::parse_without(nested.meta_item()?, hidden_cfg).ok()?,
+            _ => None,
+        })
+        .fold(Cfg::True, |cfg, new_cfg| cfg & new_cfg);
+    if cfg == Cfg::True { None } else { Some(cfg) }
 }
 
 struct CfgPropagator {
     parent_cfg: Option<Arc<Cfg>>,
+    /// The configuration options to leave out of the inferred `doc(cfg)`, if it is inferred.
+    hidden_cfg: Option<FxHashSet<Cfg>>,
 }
 
 impl DocFolder for CfgPropagator {
     fn fold_item(&mut self, mut item: Item) -> Option<Item> {
         let old_parent_cfg = self.parent_cfg.clone();
 
+        if let Some(auto_cfg) =
+            self.hidden_cfg.as_ref().and_then(|hidden_cfg| auto_cfg(&item.attrs, hidden_cfg))
+        {
+            // Keep the `doc(cfg)` that doesn't come from attributes, like the one of
+            // `#[target_feature]`.
+            item.attrs.cfg = Some(match item.attrs.cfg.take() {
+                Some(mut cfg) => {
+                    *Arc::make_mut(&mut cfg) &= auto_cfg;
+                    cfg
+                }
+                None => Arc::new(auto_cfg),
+            });
+        }
+
         let new_cfg = match (self.parent_cfg.take(), item.attrs.cfg.take()) {
             (None, None) => None,
             (Some(rc), None) | (None, Some(rc)) => Some(rc),
@@ -0,0 +1,301 @@
+//! Finds the calls to the functions of some crates in another crate, like one of their examples,
+//! so that they can be shown in the documentation of these functions.
+//!
+//! Scraping the examples is done by documenting the crate that makes the calls with
+//! `--scrape-examples-output-path` and `--scrape-examples-target-crate`, which writes the calls
+//! to a file. This file is then given to rustdoc with `--with-examples` when documenting the
+//! target crate.
+
+use crate::html::sources;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir as hir;
+use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc_middle::hir::map::Map;
+use rustc_middle::ty::{self, TyCtxt, TypeckResults};
+use rustc_session::getopts;
+use rustc_span::{BytePos, FileName, SourceFile, Span};
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The options to scrape the calls of a crate.
+#[derive(Clone, Debug)]
+pub struct ScrapeExamplesOptions {
+    /// The file to write the calls to.
+    output_path: PathBuf,
+    /// The names of the crates whose functions are looked for.
+    target_crates: Vec<String>,
+}
+
+impl ScrapeExamplesOptions {
+    crate fn new(
+        matches: &getopts::Matches,
+        diag: &rustc_errors::Handler,
+    ) -> Result<Option<Self>, i32> {
+        let output_path = matches.opt_str("scrape-examples-output-path");
+        let target_crates = matches.opt_strs("scrape-examples-target-crate");
+        match (output_path, target_crates.is_empty()) {
+            (Some(output_path), false) => Ok(Some(ScrapeExamplesOptions {
+                output_path: PathBuf::from(output_path),
+                target_crates,
+            })),
+            (Some(_), true) => {
+                diag.struct_err(
+                    "must use --scrape-examples-target-crate with --scrape-examples-output-path",
+                )
+                .emit();
+                Err(1)
+            }
+            (None, false) => {
+                diag.struct_err(
+                    "must use --scrape-examples-output-path with --scrape-examples-target-crate",
+                )
+                .emit();
+                Err(1)
+            }
+            (None, true) => Ok(None),
+        }
+    }
+}
+
+/// A range of a source file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SyntaxRange {
+    /// The offsets in bytes of the start and the end of the range.
+    pub byte_span: (u32, u32),
+    /// The lines of the start and the end of the range, starting at 1.
+    pub line_span: (usize, usize),
+}
+
+impl SyntaxRange {
+    fn new(span: Span, file: &SourceFile) -> Self {
+        let get_pos = |pos: BytePos| (pos - file.start_pos).0;
+        let get_line = |pos: BytePos| file.lookup_line(pos).unwrap_or(0) + 1;
+
+        SyntaxRange {
+            byte_span: (get_pos(span.lo()), get_pos(span.hi())),
+            line_span: (get_line(span.lo()), get_line(span.hi())),
+        }
+    }
+}
+
+/// A call to a function of a target crate.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CallLocation {
+    /// The call expression.
+    pub call_expr: SyntaxRange,
+    /// The item the call is in, which is the code shown in the documentation.
+    pub enclosing_item: SyntaxRange,
+}
+
+/// The calls to a function found in a source file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CallData {
+    pub locations: Vec<CallLocation>,
+    /// The path of the file, which is read when rendering the calls.
+    pub path: PathBuf,
+    /// The url of the source page of the file, from the root of the documentation.
+    pub url: String,
+    /// The name of the file shown in the documentation.
+    pub display_name: String,
+}
+
+/// The calls to a function, by the url of the source page of the file they are in.
+pub type FnCallLocations = BTreeMap<String, CallData>;
+
+/// The calls to the functions of the target crates, by the hash of the path of the function.
+pub type AllCallLocations = FxHashMap<String, FnCallLocations>;
+
+/// Returns the key of `def_id` in `AllCallLocations`, which is the same in every crate.
+fn call_locations_key(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    tcx.def_path_hash(def_id).0.to_hex()
+}
+
+/// Finds the calls to the functions of the target crates in the local crate, and writes them to
+/// the output path of `options`.
+crate fn run(tcx: TyCtxt<'_>, options: &ScrapeExamplesOptions) {
+    let target_crates = tcx
+        .crates()
+        .iter()
+        .copied()
+        .filter(|&cnum| {
+            let crate_name = tcx.crate_name(cnum);
+            options.target_crates.iter().any(|name| *name == *crate_name.as_str())
+        })
+        .collect();
+    let src_root = tcx
+        .sess
+        .local_crate_source_file
+        .as_deref()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let mut finder = FindCalls {
+        tcx,
+        src_root,
+        target_crates,
+        maybe_typeck_results: None,
+        calls: AllCallLocations::default(),
+    };
+    intravisit::walk_crate(&mut finder, tcx.hir().krate());
+
+    let contents = serde_json::to_string(&finder.calls).expect("failed to serialize the calls");
+    if let Err(e) = fs::write(&options.output_path, contents) {
+        tcx.sess.err(&format!(
+            "failed to write scraped examples to `{}`: {}",
+            options.output_path.display(),
+            e
+        ));
+    }
+}
+
+/// Reads the calls scraped in the files given with `--with-examples`.
+crate fn load_call_locations(
+    with_examples: Vec<String>,
+    diag: &rustc_errors::Handler,
+) -> Result<AllCallLocations, i32> {
+    let mut all_calls = AllCallLocations::default();
+    for path in with_examples {
+        let calls: AllCallLocations = match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string()))
+        {
+            Ok(calls) => calls,
+            Err(e) => {
+                diag.struct_err(&format!("failed to load examples from `{}`: {}", path, e)).emit();
+                return Err(1);
+            }
+        };
+        for (function, fn_calls) in calls {
+            all_calls.entry(function).or_default().extend(fn_calls);
+        }
+    }
+    Ok(all_calls)
+}
+
+/// Returns the calls to the functions of the local crate among `all_calls`.
+crate fn local_call_locations(
+    tcx: TyCtxt<'_>,
+    mut all_calls: AllCallLocations,
+) -> FxHashMap<DefId, FnCallLocations> {
+    let krate = tcx.hir().krate();
+    let fns = krate
+        .items
+        .values()
+        .filter(|item| matches!(item.kind, hir::ItemKind::Fn(..)))
+        .map(|item| item.hir_id)
+        .chain(
+            krate
+                .impl_items
+                .values()
+                .filter(|item| matches!(item.kind, hir::ImplItemKind::Fn(..)))
+                .map(|item| item.hir_id),
+        );
+
+    let mut calls = FxHashMap::default();
+    for hir_id in fns {
+        let def_id = tcx.hir().local_def_id(hir_id).to_def_id();
+        if let Some(fn_calls) = all_calls.remove(&call_locations_key(tcx, def_id)) {
+            calls.insert(def_id, fn_calls);
+        }
+    }
+    calls
+}
+
+struct FindCalls<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The directory the source pages are generated relative to.
+    src_root: PathBuf,
+    target_crates: Vec<CrateNum>,
+    /// The results of the type checking of the body being visited, if any.
+    maybe_typeck_results: Option<&'tcx TypeckResults<'tcx>>,
+    calls: AllCallLocations,
+}
+
+impl FindCalls<'tcx> {
+    /// Returns the function called by `expr`, if it is a call.
+    fn called_function(&self, expr: &'tcx hir::Expr<'tcx>) -> Option<DefId> {
+        let typeck_results = self.maybe_typeck_results?;
+        match expr.kind {
+            hir::ExprKind::Call(ref f, _) => match typeck_results.node_type_opt(f.hir_id)?.kind() {
+                ty::FnDef(def_id, _) => Some(*def_id),
+                _ => None,
+            },
+            hir::ExprKind::MethodCall(..) => typeck_results.type_dependent_def_id(expr.hir_id),
+            _ => None,
+        }
+    }
+
+    fn add_call(&mut self, def_id: DefId, span: Span, enclosing_item_span: Span) {
+        let file = self.tcx.sess.source_map().lookup_source_file(span.lo());
+        let path = match file.name {
+            FileName::Real(ref name) => name.local_path().to_path_buf(),
+            _ => return,
+        };
+
+        let url = format!(
+            "src/{}/{}",
+            self.tcx.crate_name(LOCAL_CRATE),
+            sources::source_href(&self.src_root, &path)
+        );
+        // The path is read by another invocation of rustdoc, which may be run from another
+        // directory.
+        let abs_path =
+            env::current_dir().map(|dir| dir.join(&path)).unwrap_or_else(|_| path.clone());
+        let call_data = self
+            .calls
+            .entry(call_locations_key(self.tcx, def_id))
+            .or_default()
+            .entry(url.clone())
+            .or_insert_with(|| CallData {
+                locations: Vec::new(),
+                path: abs_path,
+                url,
+                display_name: path.display().to_string(),
+            });
+        call_data.locations.push(CallLocation {
+            call_expr: SyntaxRange::new(span, &file),
+            enclosing_item: SyntaxRange::new(enclosing_item_span, &file),
+        });
+    }
+}
+
+impl Visitor<'tcx> for FindCalls<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::All(self.tcx.hir())
+    }
+
+    fn visit_nested_body(&mut self, body_id: hir::BodyId) {
+        let old_typeck_results =
+            self.maybe_typeck_results.replace(self.tcx.typeck_body(body_id));
+        let body = self.tcx.hir().body(body_id);
+        self.visit_body(body);
+        self.maybe_typeck_results = old_typeck_results;
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        intravisit::walk_expr(self, expr);
+
+        let def_id = match self.called_function(expr) {
+            Some(def_id) if self.target_crates.contains(&def_id.krate) => def_id,
+            _ => return,
+        };
+        // Calls written by macros can't be shown.
+        if expr.span.from_expansion() {
+            return;
+        }
+        let hir = self.tcx.hir();
+        let enclosing_item_span = hir.span_with_body(hir.get_parent_item(expr.hir_id));
+        if enclosing_item_span.from_expansion() || !enclosing_item_span.contains(expr.span) {
+            return;
+        }
+        self.add_call(def_id, expr.span, enclosing_item_span);
+    }
+}
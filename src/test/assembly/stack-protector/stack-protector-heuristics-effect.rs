@@ -0,0 +1,63 @@
+// revisions: all strong basic none
+// assembly-output: emit-asm
+// only-x86_64
+// ignore-macos slightly different policy on stack protection of arrays
+// ignore-windows stack check code uses different function names
+// [all] compile-flags: -Z stack-protector=all
+// [strong] compile-flags: -Z stack-protector=strong
+// [basic] compile-flags: -Z stack-protector=basic
+// compile-flags: --crate-type rlib -C opt-level=2
+
+// Checks which functions get a stack canary for each `-Z stack-protector` strategy.
+
+extern "C" {
+    fn black_box(p: *mut u8);
+}
+
+// CHECK-LABEL: large_byte_array:
+#[no_mangle]
+pub fn large_byte_array() {
+    let mut a = [0u8; 16];
+    unsafe { black_box(a.as_mut_ptr()) };
+
+    // all: __stack_chk_fail
+    // strong: __stack_chk_fail
+    // basic: __stack_chk_fail
+    // none-NOT: __stack_chk_fail
+}
+
+// CHECK-LABEL: small_byte_array:
+#[no_mangle]
+pub fn small_byte_array() {
+    let mut a = [0u8; 4];
+    unsafe { black_box(a.as_mut_ptr()) };
+
+    // all: __stack_chk_fail
+    // strong: __stack_chk_fail
+    // basic-NOT: __stack_chk_fail
+    // none-NOT: __stack_chk_fail
+}
+
+// CHECK-LABEL: local_address_taken:
+#[no_mangle]
+pub fn local_address_taken() {
+    let mut x = 0u32;
+    unsafe { black_box(&mut x as *mut u32 as *mut u8) };
+
+    // all: __stack_chk_fail
+    // strong: __stack_chk_fail
+    // basic-NOT: __stack_chk_fail
+    // none-NOT: __stack_chk_fail
+}
+
+// CHECK-LABEL: no_locals:
+#[no_mangle]
+pub fn no_locals(x: u32) -> u32 {
+    unsafe { black_box(0 as *mut u8) };
+    x.wrapping_add(1)
+
+    // all: __stack_chk_fail
+    // strong-NOT: __stack_chk_fail
+    // basic-NOT: __stack_chk_fail
+    // none-NOT: __stack_chk_fail
+}
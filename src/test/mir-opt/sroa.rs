@@ -0,0 +1,59 @@
+// compile-flags: -O -C debuginfo=0
+// ignore-wasm32-bare compiled with panic=abort by default
+
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+struct Dropping {
+    x: u32,
+    y: u32,
+}
+
+impl Drop for Dropping {
+    fn drop(&mut self) {}
+}
+
+// EMIT_MIR sroa.tuple.ScalarReplacementOfAggregates.diff
+fn tuple() -> i32 {
+    let mut x = (42, 43);
+    x.1 = 99;
+    x.0 + x.1
+}
+
+// EMIT_MIR sroa.structs.ScalarReplacementOfAggregates.diff
+fn structs(a: u32) -> u32 {
+    let p = Point { x: a, y: 3 };
+    p.x
+}
+
+// A local whose field is borrowed is not replaced.
+// EMIT_MIR sroa.borrowed.ScalarReplacementOfAggregates.diff
+fn borrowed() -> u32 {
+    let p = Point { x: 1, y: 2 };
+    let r = &p.x;
+    *r
+}
+
+// A local that is used as a whole is not replaced.
+// EMIT_MIR sroa.escaping.ScalarReplacementOfAggregates.diff
+fn escaping() -> Point {
+    let p = Point { x: 1, y: 2 };
+    p
+}
+
+// A local whose type implements `Drop` is not replaced.
+// EMIT_MIR sroa.dropping.ScalarReplacementOfAggregates.diff
+fn dropping() -> u32 {
+    let d = Dropping { x: 1, y: 2 };
+    d.x
+}
+
+fn main() {
+    tuple();
+    structs(1);
+    borrowed();
+    escaping();
+    dropping();
+}
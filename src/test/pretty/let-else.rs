@@ -0,0 +1,9 @@
+#![feature(let_else)]
+
+fn first(v: &[i32]) -> i32 {
+    let [x, ..] = v else { return 0; };
+    let Some(y): Option<i32> = v.get(1).copied() else { panic!("no second element") };
+    *x + y
+}
+
+fn main() { assert_eq!(first(&[1, 2]), 3); }
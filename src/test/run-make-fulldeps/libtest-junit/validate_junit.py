@@ -0,0 +1,29 @@
+#!/usr/bin/env python
+
+import sys
+import xml.etree.ElementTree as ET
+
+# Parse the report in order to ensure it is a valid XML document, then check
+# the outcome recorded for each of the tests in f.rs. The durations vary from
+# run to run, so the output can't be compared to a reference file.
+show_output = "--show-output" in sys.argv[1:]
+root = ET.fromstring(sys.stdin.read())
+
+assert root.tag == "testsuites"
+suite = root.find("testsuite")
+assert suite.get("tests") == "4"
+assert suite.get("failures") == "1"
+assert suite.get("skipped") == "1"
+
+cases = dict((case.get("name"), case) for case in suite.iter("testcase"))
+assert sorted(cases.keys()) == ["a", "b", "c", "d"]
+for case in cases.values():
+    assert case.get("classname") == "crate"
+    float(case.get("time"))
+
+assert cases["a"].find("failure") is None
+assert (cases["a"].find("system-out") is not None) == show_output
+assert cases["b"].find("failure") is not None
+assert cases["b"].find("system-out") is not None
+assert cases["c"].find("failure") is None
+assert cases["d"].find("skipped") is not None
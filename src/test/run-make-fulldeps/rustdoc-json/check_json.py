@@ -0,0 +1,82 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+with open(sys.argv[1]) as f:
+    krate = json.load(f)
+
+assert krate["format_version"] == 1
+assert krate["version"] == "1.0.0"
+assert krate["includes_private"] is False
+
+index = krate["index"]
+root = index[krate["root"]]
+assert root["kind"] == "module"
+assert root["name"] == "foo"
+assert root["docs"] == "The crate root."
+assert root["inner"]["is_crate"] is True
+
+
+def child_id(parent, name):
+    for id in parent["inner"]["items"]:
+        if id in index and index[id]["name"] == name:
+            return id
+    raise AssertionError("no item named {} in {}".format(name, parent["name"]))
+
+
+def child(parent, name):
+    return index[child_id(parent, name)]
+
+
+foo_id = child_id(root, "Foo")
+foo = index[foo_id]
+assert foo["kind"] == "struct"
+assert foo["docs"] == "A documented struct."
+assert foo["visibility"] == "public"
+assert foo["source"]["filename"].endswith("foo.rs")
+assert foo["inner"]["struct_type"] == "plain"
+assert foo["inner"]["fields_stripped"] is True
+assert [p["name"] for p in foo["inner"]["generics"]["params"]] == ["T"]
+assert len(foo["inner"]["fields"]) == 1
+field = index[foo["inner"]["fields"][0]]
+assert field["kind"] == "struct_field"
+assert field["name"] == "field"
+assert field["inner"]["kind"] == "array"
+
+impls = [index[id] for id in foo["inner"]["impls"]]
+inherent = [i for i in impls if i["inner"]["trait"] is None]
+assert len(inherent) == 1
+method = index[inherent[0]["inner"]["items"][0]]
+assert method["kind"] == "method"
+assert method["inner"]["has_body"] is True
+assert method["inner"]["decl"]["inputs"][1][0] == "idx"
+
+bar = child(root, "Bar")
+assert bar["kind"] == "enum"
+variants = [index[id] for id in bar["inner"]["variants"]]
+assert [v["inner"]["variant_kind"] for v in variants] == ["plain", "tuple", "struct"]
+
+baz = child(root, "Baz")
+assert baz["kind"] == "trait"
+assert len(baz["inner"]["items"]) == 2
+assert len(baz["inner"]["implementors"]) == 1
+required = child(baz, "required")
+assert required["inner"]["has_body"] is False
+
+inner = child(root, "inner")
+function = child(inner, "function")
+assert function["kind"] == "function"
+assert function["inner"]["header"] == "const unsafe "
+assert function["inner"]["abi"] == "Rust"
+assert list(function["links"].values()) == [foo_id]
+
+imports = [index[id] for id in root["inner"]["items"]
+           if id in index and index[id]["kind"] == "import"]
+assert len(imports) == 1
+assert imports[0]["inner"]["source"] == "inner::function"
+assert imports[0]["inner"]["name"] == "function"
+assert imports[0]["inner"]["glob"] is False
+
+assert krate["paths"][krate["root"]]["path"] == ["foo"]
+assert krate["paths"][krate["root"]]["kind"] == "module"
@@ -0,0 +1,44 @@
+//! The crate root.
+
+/// A documented struct.
+pub struct Foo<T: Clone> {
+    /// The only field.
+    pub field: [T; 4],
+    hidden: u8,
+}
+
+impl<T: Clone> Foo<T> {
+    /// An inherent method.
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.field.get(idx)
+    }
+}
+
+/// A documented enum.
+pub enum Bar {
+    Unit,
+    Tuple(u32),
+    Named { x: i32 },
+}
+
+/// A documented trait.
+pub trait Baz {
+    fn required(&mut self) -> bool;
+
+    fn provided(&self) where Self: Sized {}
+}
+
+impl Baz for Bar {
+    fn required(&mut self) -> bool {
+        true
+    }
+}
+
+pub mod inner {
+    /// A function referencing [`crate::Foo`].
+    pub const unsafe fn function<'a>(x: &'a str) -> &'a str {
+        x
+    }
+}
+
+pub use inner::function;
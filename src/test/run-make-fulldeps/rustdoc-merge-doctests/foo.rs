@@ -0,0 +1,16 @@
+/// ```
+/// assert_eq!(foo::answer(), 42);
+/// ```
+///
+/// ```
+/// let answer = foo::answer();
+/// assert!(answer > 0);
+/// ```
+pub fn answer() -> u8 {
+    42
+}
+
+/// ```
+/// let _ = foo::Foo;
+/// ```
+pub struct Foo;
@@ -0,0 +1,4 @@
+fn main() {
+    foobar::ok();
+    foobar::Foo.bar();
+}
@@ -0,0 +1,16 @@
+// @has foobar/fn.ok.html '//*[@class="scraped-example-title"]' 'examples/ex.rs'
+// @count - '//*[@class="scraped-example-title"]/a[@href="../src/ex/ex.rs.html#1-4"]' 1
+// @has - '//*[@class="scraped-example"]//a[@href="../src/ex/ex.rs.html#2-2"]' 'ok'
+pub fn ok() {}
+
+pub struct Foo;
+
+impl Foo {
+    // @has foobar/struct.Foo.html '//*[@class="scraped-example-title"]' 'examples/ex.rs'
+    // @has - '//*[@class="scraped-example"]//a[@href="../src/ex/ex.rs.html#3-3"]' 'bar'
+    pub fn bar(&self) {}
+}
+
+// @has foobar/fn.unused.html
+// @count - '//*[@class="scraped-examples"]' 0
+pub fn unused() {}
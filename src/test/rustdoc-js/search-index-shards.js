@@ -0,0 +1,30 @@
+const QUERY = [
+    'beta',
+    'zeta',
+    'omega',
+];
+
+const EXPECTED = [
+    {
+        'others': [
+            { 'path': 'search_index_shards::alpha::Zeta', 'name': 'beta' },
+        ],
+    },
+    {
+        'others': [
+            { 'path': 'search_index_shards::alpha', 'name': 'Zeta' },
+            { 'path': 'search_index_shards::alpha::Zeta', 'name': 'zeta_len' },
+        ],
+    },
+    {
+        'others': [
+            {
+                'path': 'search_index_shards',
+                'name': 'gamma',
+                'alias': 'omega',
+                'href': '../search_index_shards/fn.gamma.html',
+                'is_alias': true
+            },
+        ],
+    },
+];
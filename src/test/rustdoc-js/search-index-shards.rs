@@ -0,0 +1,13 @@
+#![feature(doc_alias)]
+
+pub mod alpha {
+    pub struct Zeta;
+
+    impl Zeta {
+        pub fn beta() {}
+        pub fn zeta_len() {}
+    }
+}
+
+#[doc(alias = "omega")]
+pub fn gamma() {}
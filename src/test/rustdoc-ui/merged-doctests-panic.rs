@@ -0,0 +1,19 @@
+// A merged doctest that panics reports the line of the panic in the documented file.
+
+// edition:2018
+// compile-flags:--test --test-args=--test-threads=1 -Z unstable-options --merge-doctests
+// rustc-env:RUST_BACKTRACE=0
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// failure-status: 101
+
+/// ```
+/// assert_eq!(6 * 7, 42);
+/// ```
+pub struct SomeStruct;
+
+/// ```
+/// let answer = 6 * 7;
+/// assert_eq!(answer, 42);
+/// panic!("oh no");
+/// ```
+pub struct OtherStruct;
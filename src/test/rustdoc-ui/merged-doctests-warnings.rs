@@ -0,0 +1,12 @@
+// The warnings of merged doctests are shown with `--display-warnings`.
+
+// edition:2018
+// compile-flags:--test --test-args=--test-threads=1 -Z unstable-options --merge-doctests
+// compile-flags:--display-warnings
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// check-pass
+
+/// ```
+/// let x = 42;
+/// ```
+pub struct Foo;
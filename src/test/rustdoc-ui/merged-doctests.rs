@@ -0,0 +1,67 @@
+// edition:2018
+// compile-flags:--test --test-args=--test-threads=1 -Z unstable-options --merge-doctests
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// normalize-stderr-test: "src/test/rustdoc-ui" -> "$$DIR"
+// check-pass
+
+// Doctests that can be merged are compiled into one binary per edition, but are still run and
+// reported one by one.
+
+/// ```
+/// assert_eq!(6 * 7, 42);
+/// ```
+///
+/// ```
+/// fn main() -> Result<(), std::num::ParseIntError> {
+///     assert_eq!("42".parse::<u8>()?, 42);
+///     Ok(())
+/// }
+/// ```
+///
+/// ```
+/// let x: u8 = "42".parse()?;
+/// assert_eq!(x, 42);
+/// Ok::<(), std::num::ParseIntError>(())
+/// ```
+///
+/// ```should_panic
+/// assert_eq!(6 * 7, 0);
+/// ```
+///
+/// ```
+/// #![allow(unused_variables)]
+/// let answer = 42;
+/// ```
+pub fn answer() -> u8 {
+    42
+}
+
+// In the 2015 edition, paths in `use` items are relative to the crate root, so this doctest
+// does not compile in a module of the merged crate, and is compiled on its own instead.
+/// ```edition2015
+/// mod inner {
+///     pub fn answer() -> u8 { 42 }
+/// }
+/// use inner::answer;
+/// fn main() {
+///     assert_eq!(answer(), 42);
+/// }
+/// ```
+pub struct Inner;
+
+// The items of a doctest are in a module of the merged crate, so this doctest does not compile in
+// it and is compiled on its own, while the other doctests of its edition stay merged.
+/// ```
+/// fn answer() -> u8 { 42 }
+/// fn main() {
+///     assert_eq!(crate::answer(), 42);
+/// }
+/// ```
+pub struct Crate;
+
+// Merged doctests see the same arguments and environment as when compiled on their own.
+/// ```
+/// assert_eq!(std::env::args().count(), 1);
+/// assert!(std::env::var_os("__RUSTDOC_DOCTEST_INDEX").is_none());
+/// ```
+pub struct Args;
@@ -0,0 +1,3 @@
+#![crate_name = "source_code"]
+
+pub struct SourceCode;
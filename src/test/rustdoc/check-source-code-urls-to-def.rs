@@ -0,0 +1,29 @@
+// compile-flags: -Zunstable-options --generate-link-to-definition
+// aux-build:source_code.rs
+// build-aux-docs
+// ignore-cross-compile
+// ignore-tidy-linelength
+
+#![crate_name = "foo"]
+
+extern crate source_code;
+
+// @has 'src/foo/check-source-code-urls-to-def.rs.html'
+
+// @has - '//a[@href="../../src/foo/check-source-code-urls-to-def/bar.rs.html#1"]' 'bar'
+#[path = "check-source-code-urls-to-def/bar.rs"]
+pub mod bar;
+
+// @has - '//a[@href="../../src/foo/check-source-code-urls-to-def/bar.rs.html#1"]' 'Bar'
+use bar::Bar;
+
+// @count - '//a[@href="../../src/foo/check-source-code-urls-to-def.rs.html#22"]' 2
+// @has - '//a[@href="../../source_code/struct.SourceCode.html"]' 'SourceCode'
+pub struct Foo(pub Bar, pub source_code::SourceCode);
+
+pub fn foo(x: Foo) -> Foo {
+    // @has - '//a[@href="../../src/foo/check-source-code-urls-to-def.rs.html#24"]' 'x'
+    let y = x;
+    // @has - '//a[@href="../../src/foo/check-source-code-urls-to-def.rs.html#26"]' 'y'
+    y
+}
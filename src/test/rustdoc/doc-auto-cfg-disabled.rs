@@ -0,0 +1,16 @@
+// compile-flags: --cfg feature="tokio"
+
+#![feature(doc_cfg)]
+#![doc(auto_cfg = false)]
+#![crate_name = "foo"]
+
+// @has foo/fn.tokio.html
+// @count - '//*[@class="stab portability"]' 0
+#[cfg(feature = "tokio")]
+pub fn tokio() {}
+
+// @has foo/fn.explicit.html '//*[@class="stab portability"]' \
+//  'This is supported on crate feature tokio only.'
+#[cfg(feature = "tokio")]
+#[doc(cfg(feature = "tokio"))]
+pub fn explicit() {}
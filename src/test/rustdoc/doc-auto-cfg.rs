@@ -0,0 +1,45 @@
+// compile-flags: --cfg feature="tokio" --cfg feature="hidden"
+
+#![feature(doc_cfg)]
+#![doc(cfg_hide(feature = "hidden"))]
+#![crate_name = "foo"]
+
+// @has foo/fn.tokio.html '//*[@class="stab portability"]' \
+//  'This is supported on crate feature tokio only.'
+#[cfg(feature = "tokio")]
+pub fn tokio() {}
+
+// @has foo/fn.from_cfg_attr.html '//*[@class="stab portability"]' \
+//  'This is supported on crate feature tokio only.'
+#[cfg_attr(feature = "tokio", cfg(feature = "tokio"))]
+pub fn from_cfg_attr() {}
+
+// @has foo/fn.hidden.html
+// @count - '//*[@class="stab portability"]' 0
+#[cfg(feature = "hidden")]
+pub fn hidden() {}
+
+// @has foo/fn.not_test.html
+// @count - '//*[@class="stab portability"]' 0
+#[cfg(not(test))]
+pub fn not_test() {}
+
+// @has foo/fn.partly_hidden.html '//*[@class="stab portability"]' \
+//  'This is supported on crate feature tokio only.'
+#[cfg(all(feature = "tokio", feature = "hidden"))]
+pub fn partly_hidden() {}
+
+// An explicit `doc(cfg)` replaces the inferred one.
+// @has foo/fn.explicit.html '//*[@class="stab portability"]' 'This is supported on Unix only.'
+#[cfg(feature = "tokio")]
+#[doc(cfg(unix))]
+pub fn explicit() {}
+
+// @has foo/tokio_only/index.html '//*[@class="stab portability"]' \
+//  'This is supported on crate feature tokio only.'
+#[cfg(feature = "tokio")]
+pub mod tokio_only {
+    // @has foo/tokio_only/fn.inner.html '//*[@class="stab portability"]' \
+    //  'This is supported on crate feature tokio only.'
+    pub fn inner() {}
+}
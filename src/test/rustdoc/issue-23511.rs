@@ -6,7 +6,7 @@ pub mod str {
 
     #[lang = "str_alloc"]
     impl str {
-        // @has search-index.js foo
+        // @has search-index/issue_23511/f.js foo
         pub fn foo(&self) {}
     }
 }
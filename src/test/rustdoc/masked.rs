@@ -7,7 +7,7 @@
 #[doc(masked)]
 extern crate masked;
 
-// @!has 'search-index.js' 'masked_method'
+// @!has 'search-index/foo/m.js' 'masked_method'
 
 // @!has 'foo/struct.String.html' 'MaskedTrait'
 // @!has 'foo/struct.String.html' 'masked_method'
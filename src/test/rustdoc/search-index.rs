@@ -2,25 +2,26 @@
 
 use std::ops::Deref;
 
-// @has search-index.js Foo
+// @has search-index.js rustdoc_test
+// @has search-index/rustdoc_test/f.js Foo
 pub use private::Foo;
 
 mod private {
     pub struct Foo;
     impl Foo {
-        pub fn test_method() {} // @has - test_method
-        fn priv_method() {} // @!has - priv_method
+        pub fn test_method() {} // @has search-index/rustdoc_test/t.js test_method
+        fn priv_method() {} // @!has search-index/rustdoc_test/p.js
     }
 
     pub trait PrivateTrait {
-        fn trait_method(&self) {} // @!has - priv_method
+        fn trait_method(&self) {} // @!has search-index/rustdoc_test/t.js trait_method
     }
 }
 
 pub struct Bar;
 
 impl Deref for Bar {
-    // @!has search-index.js Target
+    // @!has search-index/rustdoc_test/t.js Target
     type Target = Bar;
     fn deref(&self) -> &Bar { self }
 }
@@ -0,0 +1,20 @@
+// Check warning for names that were not declared with `names()`.
+//
+// check-pass
+// compile-flags: --check-cfg=names(foo,bar) -Z unstable-options
+
+#[cfg(foo)]
+pub fn foo() {}
+
+#[cfg(bar)]
+pub fn bar() {}
+
+#[cfg(baz)]
+//~^ WARNING unexpected `cfg` condition name
+pub fn baz() {}
+
+#[cfg(any(foo, bra))]
+//~^ WARNING unexpected `cfg` condition name
+pub fn foo_or_bra() {}
+
+pub fn main() {}
@@ -0,0 +1,17 @@
+// Check warning for unexpected configuration values.
+//
+// check-pass
+// compile-flags: --check-cfg=values(feature,"serde","full") -Z unstable-options
+
+#[cfg(feature = "sedre")]
+//~^ WARNING unexpected `cfg` condition value
+pub fn f() {}
+
+#[cfg(feature = "serde")]
+pub fn g() {}
+
+#[cfg_attr(feature = "ful", inline)]
+//~^ WARNING unexpected `cfg` condition value
+pub fn h() {}
+
+pub fn main() {}
@@ -0,0 +1,20 @@
+// Check warning for unexpected configuration names, using the well-known names.
+//
+// check-pass
+// compile-flags: --check-cfg=names() -Z unstable-options
+
+#[cfg(tagret_os = "linux")]
+//~^ WARNING unexpected `cfg` condition name
+fn tagret_os() {}
+
+#[cfg(target_os = "linux")]
+fn target_os() {}
+
+#[cfg(features = "foo")]
+//~^ WARNING unexpected `cfg` condition name
+fn features() {}
+
+fn main() {
+    if cfg!(debug_assertion) {}
+    //~^ WARNING unexpected `cfg` condition name
+}
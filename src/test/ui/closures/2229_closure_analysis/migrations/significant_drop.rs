@@ -0,0 +1,63 @@
+// run-rustfix
+
+#![deny(rust_2021_incompatible_closure_captures)]
+
+// A closure that moves part of a variable whose type needs dropping would no longer drop the
+// rest of that variable along with itself in the 2021 edition, since only that part would be
+// captured.
+
+#[derive(Debug)]
+struct Foo(i32);
+impl Drop for Foo {
+    fn drop(&mut self) {
+        println!("{:?} dropped", self.0);
+    }
+}
+
+fn one_variable() {
+    let t = (Foo(0), Foo(1));
+    let c = || { let _t = t.0; };
+    //~^ ERROR: drop order will change in Rust 2021
+    c();
+}
+
+fn several_variables() {
+    let t = (Foo(0), Foo(1));
+    let u = (Foo(2), Foo(3));
+    let c = || { let _t = t.0; let _u = u.1; };
+    //~^ ERROR: drop order will change in Rust 2021
+    c();
+}
+
+fn no_migration_needed() {
+    // `t` is moved as a whole.
+    let t = (Foo(0), Foo(1));
+    let c = || { let _t = t; };
+    c();
+
+    // Only a part of `u` is used, but it isn't moved.
+    let u = (Foo(2), Foo(3));
+    let c = || println!("{:?}", u.0);
+    c();
+
+    // `v` doesn't need dropping.
+    let v = (String::new().len(), 0);
+    let c = move || { let _v = v.0; };
+    c();
+
+    // Only the `Copy` field of `w` is left behind, the part that needs dropping is moved.
+    let w = (Foo(4), 0i32);
+    let c = move || { let _w = w.0; };
+    c();
+
+    // Every field of `x` is moved, so all of it is still dropped along with the closure.
+    let x = (0i32, Foo(5));
+    let c = move || { let _x0 = x.0; let _x1 = x.1; };
+    c();
+}
+
+fn main() {
+    one_variable();
+    several_variables();
+    no_migration_needed();
+}
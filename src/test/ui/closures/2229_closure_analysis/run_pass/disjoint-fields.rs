@@ -0,0 +1,92 @@
+// run-pass
+// edition:2021
+
+// Closures capture the places they use rather than whole variables, so other parts of
+// those variables can be borrowed or moved while the closures are alive.
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct Wrapper {
+    point: Point,
+    name: String,
+}
+
+fn mutable_borrows() {
+    let mut p = Point { x: 1, y: 2 };
+    let x = &mut p.x;
+    let mut c = || p.y += 10;
+    *x += 1;
+    c();
+    assert_eq!((p.x, p.y), (2, 12));
+}
+
+fn moves() {
+    let w = Wrapper { point: Point { x: 1, y: 2 }, name: String::from("w") };
+    // Only `w.name` is moved into the closure.
+    let c = move || w.name.len();
+    assert_eq!(w.point.x + w.point.y, 3);
+    assert_eq!(c(), 1);
+}
+
+fn nested_closures() {
+    let mut t = (String::from("a"), (1, 2));
+    let first = &t.0;
+    let mut outer = || {
+        let mut inner = || (t.1).1 += 1;
+        inner();
+    };
+    outer();
+    assert_eq!(first, "a");
+    assert_eq!((t.1).1, 3);
+}
+
+fn through_references() {
+    let mut p = Point { x: 1, y: 2 };
+    let r = &mut p;
+    let y = &r.y;
+    let mut c = || r.x += 5;
+    c();
+    assert_eq!(*y, 2);
+    assert_eq!(p.x, 6);
+}
+
+fn through_boxes() {
+    let mut b = Box::new(Point { x: 1, y: 2 });
+    let y = &b.y;
+    let mut c = || b.x = 5;
+    c();
+    assert_eq!(*y, 2);
+    assert_eq!(b.x, 5);
+}
+
+fn arrays_are_captured_whole() {
+    let mut t = ([1, 2], 3);
+    let mut c = || t.0[1] += 1;
+    c();
+    t.1 += 1;
+    assert_eq!(t, ([1, 3], 4));
+}
+
+fn patterns() {
+    let t = (String::from("a"), String::from("bc"));
+    let u = &t.1;
+    let c = || {
+        let (a, _) = &t;
+        let _ = t.0.len();
+        a.len()
+    };
+    assert_eq!(c() + u.len(), 3);
+}
+
+fn main() {
+    mutable_borrows();
+    moves();
+    nested_closures();
+    through_references();
+    through_boxes();
+    arrays_are_captured_whole();
+    patterns();
+}
@@ -0,0 +1,32 @@
+// run-pass
+// Checks that const parameters with a default can be left out.
+#![feature(min_const_generics, const_generics_defaults)]
+
+pub struct ConstDefault<const N: usize = 3>;
+
+impl<const N: usize> ConstDefault<N> {
+    fn len(&self) -> usize {
+        N
+    }
+}
+
+pub struct ArrayVec<T, const CAP: usize = 16> {
+    data: [Option<T>; CAP],
+}
+
+pub struct MixedDefaults<T = u8, const N: usize = 4>([T; N]);
+
+pub fn main() {
+    let s = ConstDefault;
+    assert_eq!(s.len(), 3);
+    let s: ConstDefault<7> = ConstDefault;
+    assert_eq!(s.len(), 7);
+
+    let v: ArrayVec<u8> = ArrayVec { data: [None; 16] };
+    assert_eq!(v.data.len(), 16);
+
+    let m: MixedDefaults = MixedDefaults([1; 4]);
+    assert_eq!(m.0, [1u8; 4]);
+    let m: MixedDefaults<u16, 2> = MixedDefaults([2; 2]);
+    assert_eq!(m.0, [2u16; 2]);
+}
@@ -0,0 +1,11 @@
+#![feature(min_const_generics, const_generics_defaults)]
+
+struct Foo<const N: usize>;
+
+impl<const N: usize = 1> Foo<N> {}
+//~^ ERROR defaults for const parameters are only allowed
+
+fn bar<const N: usize = 1>() {}
+//~^ ERROR defaults for const parameters are only allowed
+
+fn main() {}
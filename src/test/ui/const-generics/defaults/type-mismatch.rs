@@ -0,0 +1,10 @@
+#![feature(min_const_generics, const_generics_defaults)]
+
+// Checks that const arguments equal to their default are elided in type mismatch errors.
+
+pub struct Example<T, const N: usize = 13>(T);
+
+fn main() {
+    let _: Example<u32, 13> = Example::<u8, 13>(0);
+    //~^ ERROR mismatched types
+}
@@ -3,7 +3,7 @@
 #![cfg_attr(min, feature(min_const_generics))]
 
 struct A<T = u32, const N: usize> {
-    //~^ ERROR type parameters with a default must be trailing
+    //~^ ERROR generic parameters with a default must be trailing
     arg: T,
 }
 
@@ -0,0 +1,21 @@
+#![feature(destructuring_assignment)]
+
+struct Struct {
+    a: u32,
+    b: u32,
+}
+
+struct TupleStruct(u32, u32);
+
+fn main() {
+    let (mut a, mut b) = (0, 1);
+    let s = Struct { a: 0, b: 1 };
+
+    (a, .., b, ..) = (2, 3, 4); //~ ERROR `..` can only be used once per tuple pattern
+    [a, .., b, ..] = [2, 3, 4]; //~ ERROR `..` can only be used once per slice pattern
+    TupleStruct(a, .., b, ..) = TupleStruct(2, 3);
+    //~^ ERROR `..` can only be used once per tuple struct or variant pattern
+    Struct { a, ..s } = s; //~ ERROR functional record updates are not allowed
+    let _ = _; //~ ERROR `_` can only be used on the left-hand side of an assignment
+    let _ = Struct { a: 0, .. }; //~ ERROR base expression required after `..`
+}
@@ -0,0 +1,89 @@
+// run-pass
+
+#![feature(destructuring_assignment)]
+
+struct Struct<S, T> {
+    a: S,
+    b: T,
+}
+
+struct TupleStruct<S, T>(S, T);
+
+enum Enum<S, T> {
+    SingleVariant(S, T),
+}
+
+fn swap() {
+    let (mut a, mut b) = (1, 2);
+    (a, b) = (b, a);
+    assert_eq!((a, b), (2, 1));
+}
+
+fn tuples() {
+    let (mut a, mut b, mut c) = (0, 0, 0);
+    (a, (b, c)) = (1, (2, 3));
+    assert_eq!((a, b, c), (1, 2, 3));
+    (a, .., b) = (4, 5, 6, 7);
+    assert_eq!((a, b), (4, 7));
+    (.., c) = (8, 9);
+    assert_eq!(c, 9);
+    (_, a, _) = (10, 11, 12);
+    assert_eq!(a, 11);
+    (..) = (13, 14);
+}
+
+fn slices() {
+    let (mut a, mut b) = (0, 0);
+    [a, b] = [1, 2];
+    assert_eq!((a, b), (1, 2));
+    [a, .., b] = [3, 4, 5, 6];
+    assert_eq!((a, b), (3, 6));
+    [_, a, ..] = [7, 8, 9];
+    assert_eq!(a, 8);
+}
+
+fn structs() {
+    let (mut a, mut b) = (0, 0);
+    Struct { a, b } = Struct { a: 1, b: 2 };
+    assert_eq!((a, b), (1, 2));
+    Struct { a: b, b: a } = Struct { a: 3, b: 4 };
+    assert_eq!((a, b), (4, 3));
+    Struct { a, .. } = Struct { a: 5, b: "unused" };
+    assert_eq!(a, 5);
+}
+
+fn tuple_structs() {
+    let (mut a, mut b) = (0, 0);
+    TupleStruct(a, b) = TupleStruct(1, 2);
+    assert_eq!((a, b), (1, 2));
+    TupleStruct(_, a) = TupleStruct("unused", 3);
+    assert_eq!(a, 3);
+    Enum::SingleVariant(a, ..) = Enum::SingleVariant(4, "unused");
+    assert_eq!(a, 4);
+}
+
+fn places() {
+    let mut v = vec![0, 0];
+    let mut t = (0, 0);
+    let mut x = 0;
+    let r = &mut x;
+    (v[1], t.0, *r) = (1, 2, 3);
+    assert_eq!(x, 3);
+    assert_eq!((v, t), (vec![0, 1], (2, 0)));
+}
+
+fn nested() {
+    let (mut a, mut b, mut c) = (0, 0, 0);
+    (a, Struct { a: b, b: [c, ..] }) = (1, Struct { a: 2, b: [3, 4, 5] });
+    assert_eq!((a, b, c), (1, 2, 3));
+}
+
+fn main() {
+    swap();
+    tuples();
+    slices();
+    structs();
+    tuple_structs();
+    places();
+    nested();
+}
@@ -0,0 +1,15 @@
+// Without `capture_disjoint_fields`, closures capture whole variables, so a closure that
+// only uses one field of a struct conflicts with a borrow of another field.
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    let mut p = Point { x: 1, y: 2 };
+    let x = &mut p.x;
+    let mut c = || p.y += 1; //~ ERROR cannot borrow `p` as mutable more than once at a time
+    *x += 1;
+    c();
+}
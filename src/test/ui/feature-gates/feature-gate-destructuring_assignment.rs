@@ -0,0 +1,6 @@
+fn main() {
+    let (mut a, mut b) = (0, 1);
+    (a, b) = (b, a); //~ ERROR destructuring assignments are unstable
+    assert_eq!((a, b), (1, 0));
+    _ = a; //~ ERROR destructuring assignments are unstable
+}
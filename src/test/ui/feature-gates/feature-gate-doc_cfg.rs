@@ -1,2 +1,5 @@
+#![doc(cfg_hide(test))] //~ ERROR: `#[doc(cfg_hide)]` is experimental
+#![doc(auto_cfg = false)] //~ ERROR: `#[doc(auto_cfg)]` is experimental
+
 #[doc(cfg(unix))] //~ ERROR: `#[doc(cfg)]` is experimental
 fn main() {}
@@ -1,4 +1,4 @@
 struct Foo<T, U = [u8; std::mem::size_of::<T>()]>(T, U);
-//~^ ERROR constant values inside of type parameter defaults
+//~^ ERROR constant values inside of generic parameter defaults
 
 fn main() {}
@@ -0,0 +1,12 @@
+// run-rustfix
+
+#![feature(let_else)]
+
+fn main() {
+    let Some(1) = { Some(1) } else { //~ ERROR right curly brace `}` before `else`
+        return;
+    };
+    let Some(1) = if true { Some(1) } else { None } else { //~ ERROR right curly brace
+        return;
+    };
+}
@@ -0,0 +1,14 @@
+#![feature(let_else)]
+
+fn value() {
+    let Some(_x) = Some(1) else { Some(2) }; //~ ERROR does not diverge
+}
+
+fn empty() {
+    let Some(_x) = Some(1) else {}; //~ ERROR does not diverge
+}
+
+fn main() {
+    value();
+    empty();
+}
@@ -0,0 +1,43 @@
+// run-pass
+
+#![feature(let_else)]
+
+fn first_even(v: &[u32]) -> Option<u32> {
+    let Some(&x) = v.iter().find(|&&x| x % 2 == 0) else { return None };
+    Some(x)
+}
+
+fn parse_or_zero(s: &str) -> u32 {
+    let Ok(n) = s.parse::<u32>() else {
+        return 0;
+    };
+    n
+}
+
+fn sum_pairs(pairs: &[(u32, Option<u32>)]) -> u32 {
+    let mut sum = 0;
+    for &(a, b) in pairs {
+        let Some(b): Option<u32> = b else { continue };
+        sum += a + b;
+    }
+    sum
+}
+
+fn assert_some(o: Option<u32>) {
+    let Some(_) = o else { panic!("expected `Some`") };
+}
+
+fn main() {
+    assert_eq!(first_even(&[1, 3, 4, 6]), Some(4));
+    assert_eq!(first_even(&[1, 3]), None);
+    assert_eq!(parse_or_zero("12"), 12);
+    assert_eq!(parse_or_zero("twelve"), 0);
+    assert_eq!(sum_pairs(&[(1, Some(2)), (3, None), (4, Some(5))]), 12);
+    assert_some(Some(1));
+
+    // Binding by reference does not move out of the initializer.
+    let x = Some(String::from("hello"));
+    let Some(ref s) = x else { unreachable!() };
+    assert_eq!(s, "hello");
+    assert_eq!(x.as_deref(), Some("hello"));
+}
@@ -0,0 +1,9 @@
+#![feature(let_else)]
+
+fn main() {
+    let Some(x) = Some(1) else {
+        let _ = x; //~ ERROR cannot find value `x` in this scope
+        return;
+    };
+    let _ = x;
+}
@@ -0,0 +1,12 @@
+// check-pass
+// compile-flags: -Z emit-future-incompat-report
+
+// Lints that will become hard errors are part of the report even when they are allowed.
+
+#![allow(patterns_in_fns_without_body)]
+
+trait Tr {
+    fn f(mut arg: u8);
+}
+
+fn main() {}
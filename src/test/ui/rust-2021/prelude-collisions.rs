@@ -0,0 +1,48 @@
+// run-rustfix
+// edition:2018
+// check-pass
+
+#![warn(rust_2021_compatibility)]
+
+trait TryIntoU32 {
+    fn try_into(&self) -> Result<u32, ()>;
+}
+
+impl TryIntoU32 for u8 {
+    fn try_into(&self) -> Result<u32, ()> {
+        Ok(*self as u32)
+    }
+}
+
+trait TryFromU8: Sized {
+    fn try_from(x: u8) -> Result<Self, ()>;
+}
+
+impl TryFromU8 for u32 {
+    fn try_from(x: u8) -> Result<Self, ()> {
+        Ok(x as u32)
+    }
+}
+
+fn main() {
+    let _: u32 = 3u8.try_into().unwrap();
+    //~^ WARNING trait method `try_into` will become ambiguous in Rust 2021
+    //~| WARNING this was previously accepted by the compiler but is being phased out
+
+    let _: u32 = Box::new(3u8).try_into().unwrap();
+    //~^ WARNING trait method `try_into` will become ambiguous in Rust 2021
+    //~| WARNING this was previously accepted by the compiler but is being phased out
+
+    let (a, b) = (1u8, 2u8);
+    let _: u32 = (a + b).try_into().unwrap();
+    //~^ WARNING trait method `try_into` will become ambiguous in Rust 2021
+    //~| WARNING this was previously accepted by the compiler but is being phased out
+
+    let _ = u32::try_from(3u8).unwrap();
+    //~^ WARNING trait-associated function `try_from` will become ambiguous in Rust 2021
+    //~| WARNING this was previously accepted by the compiler but is being phased out
+
+    // Already fully qualified, nothing to migrate.
+    let _: u32 = TryIntoU32::try_into(&3u8).unwrap();
+    let _ = <u32 as TryFromU8>::try_from(3u8).unwrap();
+}
@@ -0,0 +1,11 @@
+// run-pass
+// edition:2021
+
+// The 2021 prelude brings the conversion and collection traits into scope.
+
+fn main() {
+    let x: u8 = 3u32.try_into().unwrap();
+    let y = u16::try_from(x).unwrap();
+    let v = Vec::from_iter(Some(y));
+    assert_eq!(v, [3]);
+}
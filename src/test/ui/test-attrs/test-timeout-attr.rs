@@ -0,0 +1,17 @@
+// compile-flags: --test
+
+#![feature(test_timeout)]
+
+#[test]
+#[test_timeout(10)]
+fn test1() {}
+
+#[test]
+#[test_timeout(0)]
+//~^ ERROR malformed `test_timeout` attribute input
+fn test2() {}
+
+#[test]
+#[test_timeout("10")]
+//~^ ERROR malformed `test_timeout` attribute input
+fn test3() {}
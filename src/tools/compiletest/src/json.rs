@@ -23,6 +23,16 @@ struct ArtifactNotification {
     artifact: PathBuf,
 }
 
+#[derive(Deserialize)]
+struct FutureIncompatReport {
+    future_incompat_report: Vec<FutureBreakageItem>,
+}
+
+#[derive(Deserialize)]
+struct FutureBreakageItem {
+    diagnostic: Diagnostic,
+}
+
 #[derive(Deserialize, Clone)]
 struct DiagnosticSpan {
     file_name: String,
@@ -78,6 +88,26 @@ pub fn extract_rendered(output: &str) -> String {
                 } else if serde_json::from_str::<ArtifactNotification>(line).is_ok() {
                     // Ignore the notification.
                     None
+                } else if let Ok(report) = serde_json::from_str::<FutureIncompatReport>(line) {
+                    if report.future_incompat_report.is_empty() {
+                        None
+                    } else {
+                        Some(format!(
+                            "Future incompatibility report: {}",
+                            report
+                                .future_incompat_report
+                                .into_iter()
+                                .map(|item| {
+                                    format!(
+                                        "Future breakage diagnostic:\n{}",
+                                        item.diagnostic
+                                            .rendered
+                                            .unwrap_or_else(|| "Not rendered".to_string())
+                                    )
+                                })
+                                .collect::<String>()
+                        ))
+                    }
                 } else {
                     print!(
                         "failed to decode compiler output as json: line: {}\noutput: {}",
@@ -108,6 +138,11 @@ fn parse_line(file_name: &str, line: &str, output: &str, proc_res: &ProcRes) ->
                 expected_errors
             }
             Err(error) => {
+                // The future-incompatibility report repeats diagnostics that were already
+                // emitted (or allowed), so it is only rendered by `extract_rendered`.
+                if serde_json::from_str::<FutureIncompatReport>(line).is_ok() {
+                    return vec![];
+                }
                 proc_res.fatal(Some(&format!(
                     "failed to decode compiler output as json: \
                      `{}`\nline: {}\noutput: {}",
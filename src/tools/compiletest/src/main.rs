@@ -442,7 +442,10 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         list: false,
         options: test::Options::new(),
         time_options: None,
+        test_timeout: None,
         force_run_in_process: false,
+        shuffle: false,
+        shuffle_seed: None,
     }
 }
 
@@ -603,6 +606,7 @@ fn make_test(config: &Config, testpaths: &TestPaths, inputs: &Stamp) -> Vec<test
                     ignore,
                     should_panic,
                     allow_fail: false,
+                    timeout: None,
                     test_type: test::TestType::Unknown,
                 },
                 testfn: make_test_closure(config, testpaths, revision),
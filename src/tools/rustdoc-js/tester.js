@@ -218,13 +218,33 @@ function lookForEntry(entry, data) {
     return null;
 }
 
-function loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate) {
+// Loads all the shards of the search index of every crate, like the search does when it needs
+// them.
+function loadSearchShards(rawSearchIndex, doc_folder, resource_suffix) {
+    for (var crate in rawSearchIndex) {
+        if (!rawSearchIndex.hasOwnProperty(crate)) {
+            continue;
+        }
+        var keys = rawSearchIndex[crate].s || [];
+        rawSearchIndex[crate].shards = {};
+        for (var i = 0; i < keys.length; ++i) {
+            var shard = readFile(path.join(doc_folder, "search-index", crate,
+                                           keys[i] + resource_suffix + ".js"));
+            rawSearchIndex[crate].shards[keys[i]] = loadContent(
+                "function addSearchShard(crate, key, shard) { exports.shard = shard; }\n" +
+                shard).shard;
+        }
+    }
+}
+
+function loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate, doc_folder, resource_suffix) {
     if (searchIndex[searchIndex.length - 1].length === 0) {
         searchIndex.pop();
     }
     searchIndex.pop();
     var fullSearchIndex = searchIndex.join("\n") + '\nexports.rawSearchIndex = searchIndex;';
     searchIndex = loadContent(fullSearchIndex);
+    loadSearchShards(searchIndex.rawSearchIndex, doc_folder, resource_suffix);
     var finalJS = "";
 
     var arraysToLoad = ["itemTypes"];
@@ -234,9 +254,10 @@ function loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate) {
                            "levenshtein_row2"];
     // execQuery first parameter is built in getQuery (which takes in the search input).
     // execQuery last parameter is built in buildIndex.
-    // buildIndex requires the hashmap from search-index.
+    // buildIndex requires the hashmap from search-index, and the shards it lists.
     var functionsToLoad = ["buildHrefAndPath", "pathSplitter", "levenshtein", "validateResult",
-                           "handleAliases", "getQuery", "buildIndex", "execQuery", "execSearch"];
+                           "handleAliases", "getQuery", "mergeSearchShards", "buildIndex",
+                           "execQuery", "execSearch"];
 
     ALIASES = {};
     finalJS += 'window = { "currentCrate": "' + crate + '" };\n';
@@ -352,7 +373,8 @@ function load_files(doc_folder, resource_suffix, crate) {
     var searchIndex = readFile(
         path.join(doc_folder, "search-index" + resource_suffix + ".js")).split("\n");
 
-    return loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate);
+    return loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate, doc_folder,
+                              resource_suffix);
 }
 
 function showHelp() {